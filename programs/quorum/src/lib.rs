@@ -1,5 +1,9 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::ed25519_program;
+use anchor_lang::solana_program::sysvar::instructions::{load_current_index_checked, load_instruction_at_checked};
 use anchor_lang::system_program;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{self, Mint, Token, TokenAccount};
 
 declare_id!("FC1476pqPa9YtMiXVk2QTFMNEjfh8P16HiEM3DihHhqy");
 
@@ -11,30 +15,550 @@ declare_id!("FC1476pqPa9YtMiXVk2QTFMNEjfh8P16HiEM3DihHhqy");
 pub mod quorum {
     use super::*;
 
-    /// Create an option contract — fan pays premium SOL to lock in
-    /// the right to buy tickets at face value until expiry.
-    /// The options market reveals true demand intensity.
-    pub fn create_option(
-        ctx: Context<CreateOption>,
+    /// One-time setup of the global config. The signer becomes the
+    /// program authority — run this right after deploy.
+    pub fn initialize_config(ctx: Context<InitializeConfig>) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.authority = ctx.accounts.authority.key();
+        config.paused = false;
+        config.bump = ctx.bumps.config;
+        config.max_per_holder = 0;
+        config.protocol_fee_bps = 0;
+        config.treasury = ctx.accounts.authority.key();
+        config.expire_reward_lamports = 0;
+        config.min_premium_bps = 0;
+        config.max_quantity = DEFAULT_MAX_QUANTITY;
+        config.grace_seconds = 0;
+        config.whitelist_enabled = false;
+        config.min_expiry_seconds = 0;
+        config.action_cooldown_seconds = 0;
+        config.pending_authority = None;
+        config.dispute_window_seconds = 0;
+        config.issuance_timeout_seconds = DEFAULT_ISSUANCE_TIMEOUT_SECONDS;
+        config.max_royalty_bps = DEFAULT_MAX_ROYALTY_BPS;
+        config.writer_vesting_seconds = 0;
+        config.expiry_warning_seconds = DEFAULT_EXPIRY_WARNING_SECONDS;
+        config.min_premium_lamports = 0;
+        config.max_premium_lamports = 0;
+        config.exercise_fee_bps = 0;
+        config.late_exercise_penalty_bps = 0;
+        config.event_expiry_buffer_seconds = DEFAULT_EVENT_EXPIRY_BUFFER_SECONDS;
+        config.max_option_id_len = MAX_OPTION_ID_LEN;
+        config.max_event_name_len = MAX_EVENT_NAME_LEN;
+        config.max_event_date_len = MAX_EVENT_DATE_LEN;
+        config.max_ticket_type_len = MAX_TICKET_TYPE_LEN;
+
+        msg!("Config initialized — authority: {}", config.authority);
+        Ok(())
+    }
+
+    /// Kill switch — freezes (or unfreezes) new option creation.
+    /// Existing options can still be exercised, expired and settled.
+    pub fn set_paused(ctx: Context<SetPaused>, paused: bool) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.paused = paused;
+
+        emit!(PauseToggled {
+            authority: config.authority,
+            paused,
+        });
+
+        msg!("Program {}", if paused { "paused" } else { "unpaused" });
+        Ok(())
+    }
+
+    /// Tune protocol parameters — only the fields set in `update` change.
+    pub fn update_config(ctx: Context<UpdateConfig>, update: ConfigUpdate) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+
+        if let Some(max_per_holder) = update.max_per_holder {
+            config.max_per_holder = max_per_holder;
+        }
+        if let Some(protocol_fee_bps) = update.protocol_fee_bps {
+            require!(protocol_fee_bps <= 5000, QuorumError::InvalidProtocolFee); // max 50%
+            config.protocol_fee_bps = protocol_fee_bps;
+        }
+        if let Some(treasury) = update.treasury {
+            config.treasury = treasury;
+        }
+        if let Some(expire_reward_lamports) = update.expire_reward_lamports {
+            config.expire_reward_lamports = expire_reward_lamports;
+        }
+        if let Some(max_quantity) = update.max_quantity {
+            require!(max_quantity > 0, QuorumError::InvalidQuantity);
+            config.max_quantity = max_quantity;
+        }
+        if let Some(whitelist_enabled) = update.whitelist_enabled {
+            config.whitelist_enabled = whitelist_enabled;
+        }
+        if let Some(action_cooldown_seconds) = update.action_cooldown_seconds {
+            config.action_cooldown_seconds = action_cooldown_seconds;
+        }
+        if let Some(dispute_window_seconds) = update.dispute_window_seconds {
+            config.dispute_window_seconds = dispute_window_seconds;
+        }
+        if let Some(expiry_warning_seconds) = update.expiry_warning_seconds {
+            config.expiry_warning_seconds = expiry_warning_seconds;
+        }
+        if let Some(writer_vesting_seconds) = update.writer_vesting_seconds {
+            config.writer_vesting_seconds = writer_vesting_seconds;
+        }
+        if let Some(issuance_timeout_seconds) = update.issuance_timeout_seconds {
+            config.issuance_timeout_seconds = issuance_timeout_seconds;
+        }
+        if let Some(min_expiry_seconds) = update.min_expiry_seconds {
+            config.min_expiry_seconds = min_expiry_seconds;
+        }
+        if let Some(event_expiry_buffer_seconds) = update.event_expiry_buffer_seconds {
+            config.event_expiry_buffer_seconds = event_expiry_buffer_seconds;
+        }
+        if let Some(grace_seconds) = update.grace_seconds {
+            config.grace_seconds = grace_seconds;
+        }
+        if let Some(min_premium_bps) = update.min_premium_bps {
+            require!(min_premium_bps <= 10_000, QuorumError::InvalidMinPremium);
+            config.min_premium_bps = min_premium_bps;
+        }
+        if let Some(min_premium_lamports) = update.min_premium_lamports {
+            config.min_premium_lamports = min_premium_lamports;
+        }
+        if let Some(max_premium_lamports) = update.max_premium_lamports {
+            config.max_premium_lamports = max_premium_lamports;
+        }
+        require!(
+            config.max_premium_lamports == 0 || config.min_premium_lamports <= config.max_premium_lamports,
+            QuorumError::InvalidPremiumBounds
+        );
+        if let Some(exercise_fee_bps) = update.exercise_fee_bps {
+            require!(exercise_fee_bps <= 5000, QuorumError::InvalidProtocolFee); // max 50%
+            config.exercise_fee_bps = exercise_fee_bps;
+        }
+        if let Some(late_exercise_penalty_bps) = update.late_exercise_penalty_bps {
+            require!(late_exercise_penalty_bps <= 10_000, QuorumError::InvalidPenaltyBps);
+            config.late_exercise_penalty_bps = late_exercise_penalty_bps;
+        }
+        if let Some(max_royalty_bps) = update.max_royalty_bps {
+            require!(max_royalty_bps <= 10_000, QuorumError::InvalidRoyalty);
+            config.max_royalty_bps = max_royalty_bps;
+        }
+        // String caps can only tighten what OptionContract reserves space for
+        if let Some(max_option_id_len) = update.max_option_id_len {
+            require!(
+                max_option_id_len > 0 && max_option_id_len <= MAX_OPTION_ID_LEN,
+                QuorumError::InvalidStringLimit
+            );
+            config.max_option_id_len = max_option_id_len;
+        }
+        if let Some(max_event_name_len) = update.max_event_name_len {
+            require!(
+                max_event_name_len > 0 && max_event_name_len <= MAX_EVENT_NAME_LEN,
+                QuorumError::InvalidStringLimit
+            );
+            config.max_event_name_len = max_event_name_len;
+        }
+        if let Some(max_event_date_len) = update.max_event_date_len {
+            // Dates are YYYY-MM-DD, so anything shorter rejects every date
+            require!(
+                max_event_date_len >= 10 && max_event_date_len <= MAX_EVENT_DATE_LEN,
+                QuorumError::InvalidStringLimit
+            );
+            config.max_event_date_len = max_event_date_len;
+        }
+        if let Some(max_ticket_type_len) = update.max_ticket_type_len {
+            require!(
+                max_ticket_type_len > 0 && max_ticket_type_len <= MAX_TICKET_TYPE_LEN,
+                QuorumError::InvalidStringLimit
+            );
+            config.max_ticket_type_len = max_ticket_type_len;
+        }
+
+        msg!("Config updated by {}", config.authority);
+        Ok(())
+    }
+
+    /// Step one of an authority handoff: nominate the next authority.
+    /// Nothing changes until the nominee accepts; proposing again (or None)
+    /// replaces or withdraws the nomination.
+    pub fn propose_authority(ctx: Context<ProposeAuthority>, new_authority: Option<Pubkey>) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.pending_authority = new_authority;
+
+        emit!(AuthorityProposed {
+            authority: config.authority,
+            pending_authority: new_authority,
+        });
+
+        msg!("Authority handoff proposed: {:?}", new_authority);
+        Ok(())
+    }
+
+    /// Step two: the nominee signs to take over, proving it controls the key.
+    pub fn accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        let new_authority = ctx.accounts.new_authority.key();
+        require!(
+            config.pending_authority == Some(new_authority),
+            QuorumError::NotPendingAuthority
+        );
+
+        let old_authority = config.authority;
+        config.authority = new_authority;
+        config.pending_authority = None;
+
+        emit!(AuthorityAccepted {
+            old_authority,
+            new_authority,
+        });
+
+        msg!("Authority transferred: {} → {}", old_authority, new_authority);
+        Ok(())
+    }
+
+    /// Approve a venue wallet to write options while the whitelist is on.
+    pub fn add_writer(ctx: Context<AddWriter>, writer: Pubkey) -> Result<()> {
+        let entry = &mut ctx.accounts.writer_entry;
+        entry.writer = writer;
+        entry.bump = ctx.bumps.writer_entry;
+
+        emit!(WriterWhitelisted { writer, approved: true });
+
+        msg!("Writer whitelisted: {}", writer);
+        Ok(())
+    }
+
+    /// Revoke a venue's approval. Options it already wrote are unaffected.
+    pub fn remove_writer(ctx: Context<RemoveWriter>) -> Result<()> {
+        let writer = ctx.accounts.writer_entry.writer;
+
+        emit!(WriterWhitelisted { writer, approved: false });
+
+        msg!("Writer removed from whitelist: {}", writer);
+        Ok(())
+    }
+
+    /// Venue writes a Dutch-auction option: the premium starts at
+    /// params.premium_lamports and slides linearly down to premium_floor at
+    /// decay_end, then stays flat. Nobody holds it until a fan claims it —
+    /// the price at which it clears is the demand signal.
+    pub fn write_dutch_option(
+        ctx: Context<WriteDutchOption>,
+        mut params: CreateOptionParams,
+        premium_floor: u64,
+        decay_end: i64,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.option_contract.created_at == 0,
+            QuorumError::OptionIdTaken
+        );
+
+        let now = current_timestamp(&ctx.accounts.clock)?;
+        // The writer signs for its own listing, whoever params names
+        params.writer = Some(ctx.accounts.writer.key());
+        // The floor is the cheapest it will ever clear, so that's what must clear the minimum;
+        // the starting price is the dearest, so that's what the ceiling bounds
+        let writer = validate_create(
+            &ctx.accounts.config,
+            &ctx.accounts.writer_entry,
+            &ctx.accounts.event_registry,
+            &mut params,
+            ctx.accounts.writer.key(),
+            Some(premium_floor),
+            now,
+            ctx.program_id,
+        )?;
+        require!(
+            premium_floor > 0 && premium_floor <= params.premium_lamports,
+            QuorumError::InvalidDutchAuction
+        );
+        require!(
+            decay_end > now && decay_end <= params.expiry,
+            QuorumError::InvalidDutchAuction
+        );
+        // The buyer isn't known yet, so there's no group to split it among
+        // and no one to prefund an auto-exercise strike
+        require!(params.co_holders.is_empty(), QuorumError::InvalidCoHolders);
+        require!(!params.auto_exercise, QuorumError::AutoExerciseUnsupported);
+        require!(params.identity_hash.is_none(), QuorumError::IdentityUnsupported);
+        take_event_slot(&ctx.accounts.event_registry, writer, &params.event_name, ctx.program_id)?;
+
+        let premium_start = params.premium_lamports;
+        let bump = canonical_option_bump(
+            &params.option_id,
+            params.client_nonce,
+            ctx.accounts.option_contract.key(),
+            ctx.program_id,
+        )?;
+        let option = &mut ctx.accounts.option_contract;
+        write_option_terms(
+            option,
+            params,
+            writer,
+            None,
+            None,
+            now,
+            bump,
+        );
+        // Unclaimed: no holder and nothing paid yet
+        option.holder = Pubkey::default();
+        option.premium_lamports = 0;
+        option.status = OptionStatus::Listed as u8;
+        option.premium_start = premium_start;
+        option.premium_floor = premium_floor;
+        option.decay_end = decay_end;
+
+        index_option(
+            &ctx.accounts.event_index,
+            &ctx.accounts.option_contract.event_name,
+            ctx.accounts.option_contract.key(),
+            &ctx.accounts.writer,
+            &ctx.accounts.system_program,
+            ctx.program_id,
+        )?;
+
+        let option = &ctx.accounts.option_contract;
+        emit!(DutchOptionListed {
+            option_id: option.option_id.clone(),
+            writer,
+            premium_start,
+            premium_floor,
+            decay_end,
+        });
+
+        msg!("Dutch option listed: {} — {} → {} lamports until {}",
+             option.option_id, premium_start, premium_floor, decay_end);
+        Ok(())
+    }
+
+    /// Fan claims a Dutch-auction option at the current decayed premium.
+    /// Relisted options come through here too, at their flat premium.
+    /// Events with a presale allowlist go through claim_presale_option.
+    pub fn claim_dutch_option(ctx: Context<ClaimDutchOption>) -> Result<()> {
+        require_presale_access(
+            &ctx.accounts.event_registry,
+            &ctx.accounts.option_contract,
+            ctx.accounts.holder.key(),
+            None,
+            ctx.program_id,
+        )?;
+        process_claim(ctx)
+    }
+
+    /// claim_dutch_option for a presale: `proof` shows the claimant is in
+    /// the writer's allowlist for the event (EventRegistry.presale_root).
+    pub fn claim_presale_option(ctx: Context<ClaimDutchOption>, proof: Vec<[u8; 32]>) -> Result<()> {
+        require_presale_access(
+            &ctx.accounts.event_registry,
+            &ctx.accounts.option_contract,
+            ctx.accounts.holder.key(),
+            Some(&proof),
+            ctx.program_id,
+        )?;
+        process_claim(ctx)
+    }
+
+    /// Fan who exercised but can't make the show re-offers those tickets.
+    /// Writes a fresh option (new PDA) for the exercised quantity, pointing
+    /// back at the original via parent_option. Event terms, strike and venue
+    /// royalty carry over; the reseller sets the premium and expiry and
+    /// collects the strike when the new holder exercises. The listing is
+    /// claimed at a flat premium through claim_dutch_option, which makes it
+    /// Active under the buyer. The reseller is the new option's writer, so
+    /// the listing passes validate_create and counts against its event slots.
+    pub fn relist_option(
+        ctx: Context<RelistOption>,
         option_id: String,
-        event_name: String,
-        event_date: String,
-        ticket_type: String,
-        quantity: u8,
         premium_lamports: u64,
         expiry: i64,
-        venue_royalty_bps: u16,
     ) -> Result<()> {
-        require!(quantity > 0 && quantity <= 20, QuorumError::InvalidQuantity);
-        require!(premium_lamports > 0, QuorumError::InvalidPremium);
-        require!(option_id.len() <= 32, QuorumError::StringTooLong);
-        require!(event_name.len() <= 64, QuorumError::StringTooLong);
-        require!(event_date.len() <= 16, QuorumError::StringTooLong);
-        require!(ticket_type.len() <= 32, QuorumError::StringTooLong);
-        require!(venue_royalty_bps <= 5000, QuorumError::InvalidRoyalty); // max 50%
+        require!(
+            ctx.accounts.option_contract.created_at == 0,
+            QuorumError::OptionIdTaken
+        );
+
+        let parent = &ctx.accounts.parent_option;
+        require!(parent.status_enum()? == OptionStatus::Exercised, QuorumError::NotExercised);
+        require!(
+            ctx.accounts.reseller.key() == parent.holder,
+            QuorumError::UnauthorizedHolder
+        );
+        require!(parent.exercised_qty > 0, QuorumError::NothingToRelist);
+        // Relisting is resale by another name
+        require!(parent.transferable, QuorumError::NonTransferable);
+        // A listing has no buyer yet to bind the identity to
+        require!(parent.identity_hash.is_none(), QuorumError::IdentityUnsupported);
+
+        let reseller = ctx.accounts.reseller.key();
+        let mut params = CreateOptionParams {
+            option_id,
+            event_name: parent.event_name.clone(),
+            event_date: parent.event_date.clone(),
+            ticket_type: parent.ticket_type.clone(),
+            quantity: parent.exercised_qty,
+            premium_lamports,
+            strike_lamports: parent.strike_lamports,
+            expiry,
+            venue_royalty_bps: parent.venue_royalty_bps,
+            cancel_refund_bps: parent.cancel_refund_bps,
+            writer: Some(reseller),
+            venue: Some(parent.venue),
+            exercise_window_start: 0,
+            co_holders: Vec::new(),
+            auto_exercise: false,
+            client_nonce: 0,
+            transferable: None,
+            // Same tickets, same artwork
+            metadata_uri: Some(parent.metadata_uri.clone()),
+            identity_hash: None,
+        };
+
+        let now = current_timestamp(&ctx.accounts.clock)?;
+        // The reseller writes the new option, so it passes every check a writer's create does
+        validate_create(
+            &ctx.accounts.config,
+            &ctx.accounts.writer_entry,
+            &ctx.accounts.event_registry,
+            &mut params,
+            reseller,
+            Some(premium_lamports),
+            now,
+            ctx.program_id,
+        )?;
+        take_event_slot(&ctx.accounts.event_registry, reseller, &params.event_name, ctx.program_id)?;
+        let expiry = params.expiry;
+
+        // The tickets move to the new option — they can only be relisted once
+        let parent_key = ctx.accounts.parent_option.key();
+        let quantity = params.quantity;
+        ctx.accounts.parent_option.exercised_qty = 0;
+
+        let bump = canonical_option_bump(
+            &params.option_id,
+            params.client_nonce,
+            ctx.accounts.option_contract.key(),
+            ctx.program_id,
+        )?;
+        let option = &mut ctx.accounts.option_contract;
+        write_option_terms(
+            option,
+            params,
+            reseller,
+            None,
+            None,
+            now,
+            bump,
+        );
+        // Unclaimed until a buyer pays; flat price, so the auction is already at its floor
+        option.holder = Pubkey::default();
+        option.premium_lamports = 0;
+        option.status = OptionStatus::Listed as u8;
+        option.premium_start = premium_lamports;
+        option.premium_floor = premium_lamports;
+        option.decay_end = now;
+        option.parent_option = Some(parent_key);
+
+        index_option(
+            &ctx.accounts.event_index,
+            &ctx.accounts.option_contract.event_name,
+            ctx.accounts.option_contract.key(),
+            &ctx.accounts.reseller,
+            &ctx.accounts.system_program,
+            ctx.program_id,
+        )?;
+
+        let option = &ctx.accounts.option_contract;
+        emit!(OptionRelisted {
+            option_id: option.option_id.clone(),
+            parent_option: parent_key,
+            reseller,
+            quantity,
+            premium_lamports,
+            expiry,
+        });
 
-        let clock = Clock::get()?;
-        require!(expiry > clock.unix_timestamp, QuorumError::ExpiryInPast);
+        msg!("Option relisted: {} from {} — {} tickets at {} lamports",
+             option.option_id, parent_key, quantity, premium_lamports);
+        Ok(())
+    }
+
+    /// Create a shared exercise cap for a writer's options. A venue can sell
+    /// more options than it has tickets, but no more than total_cap tickets
+    /// will ever be exercised across the pool.
+    pub fn create_pool(ctx: Context<CreatePool>, pool_id: u64, total_cap: u32) -> Result<()> {
+        require!(total_cap > 0, QuorumError::InvalidPoolCap);
+
+        let pool = &mut ctx.accounts.pool;
+        pool.writer = ctx.accounts.writer.key();
+        pool.pool_id = pool_id;
+        pool.total_cap = total_cap;
+        pool.exercised_count = 0;
+        pool.bump = ctx.bumps.pool;
+
+        msg!("Pool created: {} for writer {} — cap {} tickets", pool_id, pool.writer, total_cap);
+        Ok(())
+    }
+
+    /// Create an option contract — fan pays premium SOL to lock in
+    /// the right to buy tickets at face value (strike) until expiry.
+    /// The writer collects the premium if the option expires; it defaults
+    /// to the holder when not specified. The venue (defaults to the writer)
+    /// takes venue_royalty_bps of the strike on exercise.
+    /// exercise_window_start = 0 is American style (exercise any time);
+    /// a timestamp makes it European-ish — exercisable only from then to expiry.
+    /// Pass the writer's OptionPool to put the option under a shared cap.
+    /// The PDA is seeded by option_id and params.client_nonce, so a client
+    /// whose create timed out can resend the identical request: if the first
+    /// one landed, the retry succeeds as a no-op on the same account instead
+    /// of failing with "account in use". A different request reusing the
+    /// same option_id and nonce still fails with OptionIdTaken.
+    /// The options market reveals true demand intensity.
+    pub fn create_option(ctx: Context<CreateOption>, mut params: CreateOptionParams) -> Result<()> {
+        let now = current_timestamp(&ctx.accounts.clock)?;
+        let holder = ctx.accounts.holder.key();
+        let premium = params.premium_lamports;
+        // Resolve the terms first: a retry is compared against what the
+        // original stored, event-pinned expiry included
+        let writer = validate_create(
+            &ctx.accounts.config,
+            &ctx.accounts.writer_entry,
+            &ctx.accounts.event_registry,
+            &mut params,
+            holder,
+            Some(premium),
+            now,
+            ctx.program_id,
+        )?;
+        // init_if_needed hands us the existing account on an ID collision —
+        // a fresh one has never been stamped with created_at. A retry of a
+        // request that already landed resolves to the same PDA: succeed
+        // without touching it, so the client just re-reads the account.
+        if ctx.accounts.option_contract.created_at != 0 {
+            require!(
+                is_create_retry(&ctx.accounts.option_contract, &params, holder),
+                QuorumError::OptionIdTaken
+            );
+            msg!("Option {} already created — retry is a no-op", params.option_id);
+            return Ok(());
+        }
+
+        take_event_slot(&ctx.accounts.event_registry, writer, &params.event_name, ctx.program_id)?;
+
+        // Protocol fee goes straight from the fan to the treasury — only the
+        // net premium lands in the PDA, so its rent reserve is never touched
+        let fee = protocol_fee(&ctx.accounts.config, params.premium_lamports);
+        params.premium_lamports = params.premium_lamports.checked_sub(fee).ok_or(QuorumError::MathOverflow)?;
+
+        if fee > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.holder.to_account_info(),
+                        to: ctx.accounts.treasury.to_account_info(),
+                    },
+                ),
+                fee,
+            )?;
+        }
 
         // Transfer premium from fan to this PDA (held in the account's lamports)
         system_program::transfer(
@@ -45,204 +569,7937 @@ pub mod quorum {
                     to: ctx.accounts.option_contract.to_account_info(),
                 },
             ),
-            premium_lamports,
+            params.premium_lamports,
+        )?;
+
+        // Auto-exercise prefunds the strike, so a keeper can settle without the holder
+        let strike_escrow = strike_escrow_for(&params)?;
+        if strike_escrow > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.holder.to_account_info(),
+                        to: ctx.accounts.option_contract.to_account_info(),
+                    },
+                ),
+                strike_escrow,
+            )?;
+        }
+
+        let pool = resolve_pool(&ctx.accounts.pool, writer)?;
+        require_cooldown_elapsed(
+            &ctx.accounts.holder_stats,
+            &ctx.accounts.config,
+            now,
+        )?;
+        add_active_options(
+            &mut ctx.accounts.holder_stats,
+            holder,
+            ctx.bumps.holder_stats,
+            &ctx.accounts.config,
+            1,
         )?;
 
+        let bump = canonical_option_bump(
+            &params.option_id,
+            params.client_nonce,
+            ctx.accounts.option_contract.key(),
+            ctx.program_id,
+        )?;
         let option = &mut ctx.accounts.option_contract;
-        option.option_id = option_id;
-        option.event_name = event_name;
-        option.event_date = event_date;
-        option.ticket_type = ticket_type;
-        option.quantity = quantity;
-        option.premium_lamports = premium_lamports;
-        option.holder = ctx.accounts.holder.key();
-        option.expiry = expiry;
-        option.status = OptionStatus::Active as u8;
-        option.created_at = clock.unix_timestamp;
-        option.venue_royalty_bps = venue_royalty_bps;
-        option.bump = ctx.bumps.option_contract;
-
-        emit!(OptionCreated {
-            option_id: option.option_id.clone(),
-            event_name: option.event_name.clone(),
-            holder: option.holder,
-            premium_lamports,
-            expiry,
-        });
+        write_option_terms(
+            option,
+            params,
+            holder,
+            None,
+            pool,
+            now,
+            bump,
+        );
+        option.strike_escrowed = strike_escrow;
+        let (premium, strike, quantity) = (option.premium_lamports, option.strike_lamports, option.quantity);
+        record_event_stats(
+            &ctx.accounts.event_stats,
+            &ctx.accounts.option_contract.event_name,
+            &ctx.accounts.holder,
+            &ctx.accounts.system_program,
+            ctx.program_id,
+            |stats| {
+                stats.total_options_created += 1;
+                stats.total_premium_collected = stats.total_premium_collected.saturating_add(premium);
+                record_demand(stats, premium, strike, quantity);
+                record_price(stats, now, premium);
+            },
+        )?;
+        ctx.accounts.global_stats.record_created(1, premium);
+        index_option(
+            &ctx.accounts.event_index,
+            &ctx.accounts.option_contract.event_name,
+            ctx.accounts.option_contract.key(),
+            &ctx.accounts.holder,
+            &ctx.accounts.system_program,
+            ctx.program_id,
+        )?;
+
+        let option = &ctx.accounts.option_contract;
+        emit_lamport_flow(&option.option_id, FlowKind::Fee, fee, holder, ctx.accounts.treasury.key());
+        emit_lamport_flow(&option.option_id, FlowKind::Premium, premium, holder, option.key());
+        emit_lamport_flow(&option.option_id, FlowKind::StrikeEscrow, strike_escrow, holder, option.key());
+        emit_option_created(option, fee);
 
         msg!("Option created: {} for {} — premium: {} lamports",
-             option.option_id, option.event_name, premium_lamports);
+             option.option_id, option.event_name, option.premium_lamports);
 
         Ok(())
     }
 
-    /// Exercise an option — fan converts the option to tickets (status → Exercised)
-    /// In a real system, this would trigger ticket issuance via the venue API.
-    /// KYD: this is the CPI you'd implement on your end. Call us.
-    pub fn exercise_option(ctx: Context<ExerciseOption>) -> Result<()> {
+    /// Venue registers (or rotates) the key its off-chain pricer signs quotes with.
+    pub fn set_quote_signer(ctx: Context<SetQuoteSigner>, signer: Pubkey) -> Result<()> {
+        let quoter = &mut ctx.accounts.quoter;
+        quoter.venue = ctx.accounts.venue.key();
+        quoter.signer = signer;
+        quoter.bump = ctx.bumps.quoter;
+
+        emit!(QuoteSignerSet {
+            venue: quoter.venue,
+            signer,
+        });
+
+        msg!("Quote signer set: {} for venue {}", signer, quoter.venue);
+        Ok(())
+    }
+
+    /// Venue records that `wallet` passed its off-chain identity check, as
+    /// a hash of the verified identity. Options written with that
+    /// identity_hash are exercisable only by a wallet bound to it here, and
+    /// only move to wallets the venue has bound too. Re-binding overwrites.
+    pub fn bind_identity(ctx: Context<BindIdentity>, wallet: Pubkey, identity_hash: [u8; 32]) -> Result<()> {
+        let identity = &mut ctx.accounts.identity;
+        identity.venue = ctx.accounts.venue.key();
+        identity.wallet = wallet;
+        identity.identity_hash = identity_hash;
+        identity.bump = ctx.bumps.identity;
+
+        emit!(IdentityBound {
+            venue: identity.venue,
+            wallet,
+            identity_hash,
+        });
+
+        msg!("Identity bound: {} for venue {}", wallet, identity.venue);
+        Ok(())
+    }
+
+    /// create_option at a venue-authorized price. The venue's quote signer
+    /// signs the borsh-serialized `quote` off-chain, and the transaction
+    /// carries an ed25519 program instruction verifying it immediately
+    /// before this one. Each nonce works once per venue.
+    /// remaining_accounts: [venue's VenueQuoter PDA, writable QuoteNonce PDA
+    /// for (venue, quote.nonce), instructions sysvar].
+    pub fn create_option_with_quote<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CreateOption<'info>>,
+        params: CreateOptionParams,
+        quote: Quote,
+    ) -> Result<()> {
+        require!(ctx.remaining_accounts.len() == 3, QuorumError::InvalidQuote);
+        let (quoter_info, nonce_info, instructions) =
+            (&ctx.remaining_accounts[0], &ctx.remaining_accounts[1], &ctx.remaining_accounts[2]);
+
+        let quoter: Account<'info, VenueQuoter> = Account::try_from(quoter_info)?;
+        let holder = ctx.accounts.holder.key();
+        let venue = params.venue.unwrap_or(params.writer.unwrap_or(holder));
+        require_keys_eq!(quoter.venue, venue, QuorumError::InvalidQuote);
+        require!(
+            quote.event_name == params.event_name
+                && quote.ticket_type == params.ticket_type
+                && quote.premium_lamports == params.premium_lamports
+                && quote.expiry == params.expiry,
+            QuorumError::InvalidQuote
+        );
+        let now = current_timestamp(&ctx.accounts.clock)?;
+        require!(now <= quote.valid_until, QuorumError::QuoteExpired);
+
+        verify_ed25519_signature(instructions, &quoter.signer, &quote.try_to_vec()?)?;
+        consume_nonce(
+            nonce_info,
+            b"nonce",
+            venue,
+            quote.nonce,
+            &ctx.accounts.holder,
+            &ctx.accounts.system_program,
+            ctx.program_id,
+            now,
+        )?;
+
+        create_option(ctx, params)
+    }
+
+    /// Create an option with the premium paid in an SPL token (e.g. USDC)
+    /// instead of SOL. Tokens move from the fan's token account into an
+    /// escrow token account owned by the option PDA.
+    /// params.premium_lamports is read as token base units on this path.
+    /// Passing a strike_mint (with its strike_escrow) takes the strike in
+    /// that token too — it may differ from the premium mint — and
+    /// params.strike_lamports is then read in its base units.
+    pub fn create_option_spl(ctx: Context<CreateOptionSpl>, mut params: CreateOptionParams) -> Result<()> {
+        let now = current_timestamp(&ctx.accounts.clock)?;
+        let holder = ctx.accounts.holder.key();
+        // Resolve the terms first: a retry is compared against what the
+        // original stored, event-pinned expiry included
+        let writer = validate_create(
+            &ctx.accounts.config,
+            &ctx.accounts.writer_entry,
+            &ctx.accounts.event_registry,
+            &mut params,
+            holder,
+            None,
+            now,
+            ctx.program_id,
+        )?;
+        // init_if_needed hands us the existing account on an ID collision —
+        // a fresh one has never been stamped with created_at. A retry of a
+        // request that already landed resolves to the same PDA: succeed
+        // without touching it, so the client just re-reads the account.
+        if ctx.accounts.option_contract.created_at != 0 {
+            require!(
+                is_create_retry(&ctx.accounts.option_contract, &params, holder),
+                QuorumError::OptionIdTaken
+            );
+            msg!("Option {} already created — retry is a no-op", params.option_id);
+            return Ok(());
+        }
+
+        // The strike is in lamports, so there's no token escrow to prefund it from
+        require!(!params.auto_exercise, QuorumError::AutoExerciseUnsupported);
+        take_event_slot(&ctx.accounts.event_registry, writer, &params.event_name, ctx.program_id)?;
+
+        // Protocol fee in the premium token, straight to the treasury's token account
+        let fee = protocol_fee(&ctx.accounts.config, params.premium_lamports);
+        params.premium_lamports = params.premium_lamports.checked_sub(fee).ok_or(QuorumError::MathOverflow)?;
+
+        if fee > 0 {
+            let treasury_token = ctx
+                .accounts
+                .treasury_token
+                .as_ref()
+                .ok_or(QuorumError::InvalidTreasury)?;
+            require_keys_eq!(
+                treasury_token.owner,
+                ctx.accounts.config.treasury,
+                QuorumError::InvalidTreasury
+            );
+            require_keys_eq!(
+                treasury_token.mint,
+                ctx.accounts.premium_mint.key(),
+                QuorumError::InvalidTreasury
+            );
+            token::transfer(
+                CpiContext::new(
+                    ctx.accounts.token_program.to_account_info(),
+                    token::Transfer {
+                        from: ctx.accounts.holder_token.to_account_info(),
+                        to: treasury_token.to_account_info(),
+                        authority: ctx.accounts.holder.to_account_info(),
+                    },
+                ),
+                fee,
+            )?;
+        }
+
+        // Transfer premium tokens from fan into the PDA-owned escrow
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                token::Transfer {
+                    from: ctx.accounts.holder_token.to_account_info(),
+                    to: ctx.accounts.premium_escrow.to_account_info(),
+                    authority: ctx.accounts.holder.to_account_info(),
+                },
+            ),
+            params.premium_lamports,
+        )?;
+
+        let pool = resolve_pool(&ctx.accounts.pool, writer)?;
+        require_cooldown_elapsed(
+            &ctx.accounts.holder_stats,
+            &ctx.accounts.config,
+            now,
+        )?;
+        add_active_options(
+            &mut ctx.accounts.holder_stats,
+            holder,
+            ctx.bumps.holder_stats,
+            &ctx.accounts.config,
+            1,
+        )?;
+
+        let strike_mint = match (&ctx.accounts.strike_mint, &ctx.accounts.strike_escrow) {
+            (Some(strike_mint), Some(_)) => Some(strike_mint.key()),
+            (None, None) => None,
+            _ => return err!(QuorumError::MissingTokenAccounts),
+        };
+
+        let premium_mint = ctx.accounts.premium_mint.key();
+        let bump = canonical_option_bump(
+            &params.option_id,
+            params.client_nonce,
+            ctx.accounts.option_contract.key(),
+            ctx.program_id,
+        )?;
         let option = &mut ctx.accounts.option_contract;
+        write_option_terms(
+            option,
+            params,
+            holder,
+            Some(premium_mint),
+            pool,
+            now,
+            bump,
+        );
+        option.strike_mint = strike_mint;
+        // Token premiums aren't lamports — count the option, not its premium
+        record_event_stats(
+            &ctx.accounts.event_stats,
+            &ctx.accounts.option_contract.event_name,
+            &ctx.accounts.holder,
+            &ctx.accounts.system_program,
+            ctx.program_id,
+            |stats| stats.total_options_created += 1,
+        )?;
+        ctx.accounts.global_stats.record_created(1, 0);
+        index_option(
+            &ctx.accounts.event_index,
+            &ctx.accounts.option_contract.event_name,
+            ctx.accounts.option_contract.key(),
+            &ctx.accounts.holder,
+            &ctx.accounts.system_program,
+            ctx.program_id,
+        )?;
+
+        let option = &ctx.accounts.option_contract;
+        emit_option_created(option, fee);
+
+        msg!("Option created: {} for {} — premium: {} of mint {}",
+             option.option_id, option.event_name, option.premium_lamports, premium_mint);
 
-        require!(option.status == OptionStatus::Active as u8, QuorumError::NotActive);
+        Ok(())
+    }
+
+    /// Create several SOL-premium options in one transaction — market makers
+    /// seeding a book at event announcement. Pass one uninitialized option PDA
+    /// per entry in remaining_accounts, in the same order as `params`, each
+    /// followed by its event's EventStats, EventRegistry and EventIndex PDAs.
+    /// Every entry is validated on its own; any failure rolls back the batch.
+    pub fn create_options_batch<'info>(
+        ctx: Context<'_, '_, '_, 'info, CreateOptionsBatch<'info>>,
+        params: Vec<CreateOptionParams>,
+    ) -> Result<()> {
         require!(
-            ctx.accounts.holder.key() == option.holder,
-            QuorumError::UnauthorizedHolder
+            !params.is_empty() && params.len() <= MAX_BATCH_SIZE,
+            QuorumError::InvalidBatchSize
+        );
+        require!(
+            params.len() * 4 == ctx.remaining_accounts.len(),
+            QuorumError::BatchAccountsMismatch
         );
 
-        let clock = Clock::get()?;
-        require!(clock.unix_timestamp <= option.expiry, QuorumError::OptionExpired);
+        let now = current_timestamp(&ctx.accounts.clock)?;
+        let holder = ctx.accounts.holder.key();
+        let count = params.len();
+        let mut total_fee: u64 = 0;
+        require_cooldown_elapsed(
+            &ctx.accounts.holder_stats,
+            &ctx.accounts.config,
+            now,
+        )?;
+        add_active_options(
+            &mut ctx.accounts.holder_stats,
+            holder,
+            ctx.bumps.holder_stats,
+            &ctx.accounts.config,
+            count as u16,
+        )?;
 
-        option.status = OptionStatus::Exercised as u8;
+        for (mut option_params, accounts) in params.into_iter().zip(ctx.remaining_accounts.chunks(4)) {
+            let (option_info, stats_info, registry_info, index_info) =
+                (&accounts[0], &accounts[1], &accounts[2], &accounts[3]);
+            let premium = option_params.premium_lamports;
+            let writer = validate_create(
+                &ctx.accounts.config,
+                &ctx.accounts.writer_entry,
+                registry_info,
+                &mut option_params,
+                holder,
+                Some(premium),
+                now,
+                ctx.program_id,
+            )?;
+            take_event_slot(registry_info, writer, &option_params.event_name, ctx.program_id)?;
+            let fee = protocol_fee(&ctx.accounts.config, option_params.premium_lamports);
+            option_params.premium_lamports = option_params
+                .premium_lamports
+                .checked_sub(fee)
+                .ok_or(QuorumError::MathOverflow)?;
+            total_fee = total_fee.checked_add(fee).ok_or(QuorumError::MathOverflow)?;
+            let strike_escrow = strike_escrow_for(&option_params)?;
+            let pool = resolve_pool(&ctx.accounts.pool, writer)?;
 
-        emit!(OptionExercised {
-            option_id: option.option_id.clone(),
-            holder: option.holder,
+            let bump = canonical_option_bump(
+                &option_params.option_id,
+                option_params.client_nonce,
+                option_info.key(),
+                ctx.program_id,
+            )?;
+            require!(option_info.data_is_empty(), QuorumError::OptionIdTaken);
+
+            // The PDA signs for its own creation, then takes the premium and
+            // any prefunded strike on top of its rent
+            let nonce = option_params.client_nonce.to_le_bytes();
+            let seeds: &[&[u8]] = &[b"option", OPTION_SEED_VERSION, option_params.option_id.as_bytes(), &nonce, &[bump]];
+            create_pda_account(
+                option_info,
+                &ctx.accounts.holder.to_account_info(),
+                &ctx.accounts.system_program.to_account_info(),
+                OptionContract::MAX_SIZE,
+                seeds,
+                ctx.program_id,
+            )?;
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.holder.to_account_info(),
+                        to: option_info.clone(),
+                    },
+                ),
+                option_params.premium_lamports + strike_escrow,
+            )?;
+
+            let mut option = OptionContract::default();
+            write_option_terms(
+                &mut option,
+                option_params,
+                holder,
+                None,
+                pool,
+                now,
+                bump,
+            );
+            option.strike_escrowed = strike_escrow;
+            {
+                let mut data = option_info.try_borrow_mut_data()?;
+                option.try_serialize(&mut &mut data[..])?;
+            }
+            record_event_stats(
+                stats_info,
+                &option.event_name,
+                &ctx.accounts.holder,
+                &ctx.accounts.system_program,
+                ctx.program_id,
+                |stats| {
+                    stats.total_options_created += 1;
+                    stats.total_premium_collected =
+                        stats.total_premium_collected.saturating_add(option.premium_lamports);
+                    record_demand(stats, option.premium_lamports, option.strike_lamports, option.quantity);
+                    record_price(stats, now, option.premium_lamports);
+                },
+            )?;
+            ctx.accounts.global_stats.record_created(1, option.premium_lamports);
+            index_option(
+                index_info,
+                &option.event_name,
+                option_info.key(),
+                &ctx.accounts.holder,
+                &ctx.accounts.system_program,
+                ctx.program_id,
+            )?;
+            // The fee is logged per option even though it moves in one transfer below
+            emit_lamport_flow(&option.option_id, FlowKind::Fee, fee, holder, ctx.accounts.treasury.key());
+            emit_lamport_flow(
+                &option.option_id,
+                FlowKind::Premium,
+                option.premium_lamports,
+                holder,
+                option_info.key(),
+            );
+            emit_lamport_flow(&option.option_id, FlowKind::StrikeEscrow, strike_escrow, holder, option_info.key());
+            emit_option_created(&option, fee);
+        }
+
+        // One treasury transfer for the whole batch
+        if total_fee > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.holder.to_account_info(),
+                        to: ctx.accounts.treasury.to_account_info(),
+                    },
+                ),
+                total_fee,
+            )?;
+        }
+
+        msg!("Batch created: {} options for {}", count, holder);
+        Ok(())
+    }
+
+    /// Fan buys a compound option: the right, until `expiry`, to take the
+    /// `underlying` option at its fixed premium. The underlying terms are
+    /// validated now against the writer's event, as create_option would,
+    /// and stored on the compound PDA (seeded by the underlying option_id
+    /// and client_nonce). The child option's PDA is created now as a
+    /// Reserved placeholder, so nobody can take its address before the
+    /// holder exercises. The compound premium, less the protocol fee, is
+    /// held there until the right is used or lapses; either way it goes to
+    /// the writer.
+    pub fn create_compound_option(
+        ctx: Context<CreateCompoundOption>,
+        mut underlying: CreateOptionParams,
+        premium_lamports: u64,
+        expiry: i64,
+    ) -> Result<()> {
+        let now = current_timestamp(&ctx.accounts.clock)?;
+        let holder = ctx.accounts.holder.key();
+        // validate_create pins the writer, so the child can't default to
+        // whoever holds the compound
+        let underlying_premium = underlying.premium_lamports;
+        let writer = validate_create(
+            &ctx.accounts.config,
+            &ctx.accounts.writer_entry,
+            &ctx.accounts.event_registry,
+            &mut underlying,
+            holder,
+            Some(underlying_premium),
+            now,
+            ctx.program_id,
+        )?;
+        require!(premium_lamports > 0, QuorumError::InvalidPremium);
+        require!(expiry > now && expiry <= underlying.expiry, QuorumError::InvalidCompoundExpiry);
+
+        let fee = protocol_fee(&ctx.accounts.config, premium_lamports);
+        let premium = premium_lamports.checked_sub(fee).ok_or(QuorumError::MathOverflow)?;
+        if fee > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.holder.to_account_info(),
+                        to: ctx.accounts.treasury.to_account_info(),
+                    },
+                ),
+                fee,
+            )?;
+        }
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.holder.to_account_info(),
+                    to: ctx.accounts.compound.to_account_info(),
+                },
+            ),
+            premium,
+        )?;
+
+        let compound = &mut ctx.accounts.compound;
+        compound.holder = holder;
+        compound.writer = writer;
+        compound.premium_lamports = premium;
+        compound.expiry = expiry;
+        compound.created_at = now;
+        compound.bump = ctx.bumps.compound;
+        compound.underlying = underlying;
+
+        let compound = &ctx.accounts.compound;
+        reserve_option(
+            &mut ctx.accounts.option_contract,
+            &compound.underlying,
+            holder,
+            writer,
+            now,
+            ctx.bumps.option_contract,
+        );
+
+        let compound = &ctx.accounts.compound;
+        emit_lamport_flow(&compound.underlying.option_id, FlowKind::Fee, fee, holder, ctx.accounts.treasury.key());
+        emit_lamport_flow(&compound.underlying.option_id, FlowKind::Premium, premium, holder, compound.key());
+        emit!(CompoundOptionCreated {
+            option_id: compound.underlying.option_id.clone(),
+            client_nonce: compound.underlying.client_nonce,
+            holder,
+            writer,
+            premium_lamports: premium,
+            expiry,
+            underlying_premium: compound.underlying.premium_lamports,
         });
 
-        msg!("Option exercised: {} by {}", option.option_id, option.holder);
+        msg!("Compound option created: {} — premium: {} lamports, underlying premium: {} lamports",
+             compound.underlying.option_id, premium, compound.underlying.premium_lamports);
         Ok(())
     }
 
-    /// Expire an option — anyone can call this after expiry timestamp.
-    /// Premium stays in the PDA (venue/protocol fee).
-    /// This is how venues capture upside from options they write.
-    pub fn expire_option(ctx: Context<ExpireOption>) -> Result<()> {
+    /// Holder uses a compound option before its expiry: the stored terms
+    /// become a standard Active option, paid for at the fixed underlying
+    /// premium (plus the strike up front for auto-exercise) exactly as
+    /// create_option charges. The terms must still pass validate_create now,
+    /// as a fresh create_option would — an event expiry the writer has
+    /// pinned since applies — and the event must have a slot free. The option
+    /// is written over the placeholder reserved at creation. The compound premium
+    /// goes to the writer and the compound account closes to the holder.
+    pub fn exercise_compound_option(ctx: Context<ExerciseCompoundOption>) -> Result<()> {
+        let now = current_timestamp(&ctx.accounts.clock)?;
+        let compound = &ctx.accounts.compound;
+        require!(now <= compound.expiry, QuorumError::OptionExpired);
+        require!(
+            ctx.accounts.option_contract.status == OptionStatus::Reserved as u8,
+            QuorumError::NotReserved
+        );
+
+        let holder = ctx.accounts.holder.key();
+        let mut params = compound.underlying.clone();
+        let premium = params.premium_lamports;
+        // The writer was pinned at creation, so this resolves to compound.writer
+        let writer = validate_create(
+            &ctx.accounts.config,
+            &ctx.accounts.writer_entry,
+            &ctx.accounts.event_registry,
+            &mut params,
+            holder,
+            Some(premium),
+            now,
+            ctx.program_id,
+        )?;
+        take_event_slot(&ctx.accounts.event_registry, writer, &params.event_name, ctx.program_id)?;
+
+        // The right is used: its premium is the writer's
+        let compound_premium = compound.premium_lamports;
+        transfer_from_pda(&compound.to_account_info(), &ctx.accounts.writer, compound_premium)?;
+        emit_lamport_flow(&params.option_id, FlowKind::Premium, compound_premium, compound.key(), writer);
+
+        let fee = protocol_fee(&ctx.accounts.config, params.premium_lamports);
+        params.premium_lamports = params.premium_lamports.checked_sub(fee).ok_or(QuorumError::MathOverflow)?;
+        let strike_escrow = strike_escrow_for(&params)?;
+        if fee > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.holder.to_account_info(),
+                        to: ctx.accounts.treasury.to_account_info(),
+                    },
+                ),
+                fee,
+            )?;
+        }
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.holder.to_account_info(),
+                    to: ctx.accounts.option_contract.to_account_info(),
+                },
+            ),
+            params
+                .premium_lamports
+                .checked_add(strike_escrow)
+                .ok_or(QuorumError::MathOverflow)?,
+        )?;
+
+        let pool = resolve_pool(&ctx.accounts.pool, writer)?;
+        require_cooldown_elapsed(&ctx.accounts.holder_stats, &ctx.accounts.config, now)?;
+        add_active_options(
+            &mut ctx.accounts.holder_stats,
+            holder,
+            ctx.bumps.holder_stats,
+            &ctx.accounts.config,
+            1,
+        )?;
+
         let option = &mut ctx.accounts.option_contract;
+        let bump = option.bump;
+        write_option_terms(option, params, holder, None, pool, now, bump);
+        option.strike_escrowed = strike_escrow;
+        let (premium, strike, quantity) = (option.premium_lamports, option.strike_lamports, option.quantity);
+        record_event_stats(
+            &ctx.accounts.event_stats,
+            &ctx.accounts.option_contract.event_name,
+            &ctx.accounts.holder,
+            &ctx.accounts.system_program,
+            ctx.program_id,
+            |stats| {
+                stats.total_options_created += 1;
+                stats.total_premium_collected = stats.total_premium_collected.saturating_add(premium);
+                record_demand(stats, premium, strike, quantity);
+                record_price(stats, now, premium);
+            },
+        )?;
+        ctx.accounts.global_stats.record_created(1, premium);
+        index_option(
+            &ctx.accounts.event_index,
+            &ctx.accounts.option_contract.event_name,
+            ctx.accounts.option_contract.key(),
+            &ctx.accounts.holder,
+            &ctx.accounts.system_program,
+            ctx.program_id,
+        )?;
+
+        let option = &ctx.accounts.option_contract;
+        emit_lamport_flow(&option.option_id, FlowKind::Fee, fee, holder, ctx.accounts.treasury.key());
+        emit_lamport_flow(&option.option_id, FlowKind::Premium, premium, holder, option.key());
+        emit_lamport_flow(&option.option_id, FlowKind::StrikeEscrow, strike_escrow, holder, option.key());
+        emit_option_created(option, fee);
+        emit!(CompoundOptionExercised {
+            compound: ctx.accounts.compound.key(),
+            option: option.key(),
+            holder,
+            compound_premium,
+        });
+
+        msg!("Compound option exercised: {} — underlying option live, premium: {} lamports",
+             option.option_id, premium);
+        Ok(())
+    }
 
-        require!(option.status == OptionStatus::Active as u8, QuorumError::NotActive);
+    /// Anyone can close a compound option that lapsed unused: its premium
+    /// goes to the writer, and the rent of both it and the reserved child
+    /// back to the holder.
+    pub fn expire_compound_option(ctx: Context<ExpireCompoundOption>) -> Result<()> {
+        let now = current_timestamp(&ctx.accounts.clock)?;
+        let compound = &ctx.accounts.compound;
+        require!(now > compound.expiry, QuorumError::NotExpiredYet);
+        require!(
+            ctx.accounts.option_contract.status == OptionStatus::Reserved as u8,
+            QuorumError::NotReserved
+        );
+
+        let premium = compound.premium_lamports;
+        transfer_from_pda(&compound.to_account_info(), &ctx.accounts.writer, premium)?;
+        emit_lamport_flow(&compound.underlying.option_id, FlowKind::Premium, premium, compound.key(), compound.writer);
+        emit!(CompoundOptionExpired {
+            compound: compound.key(),
+            holder: compound.holder,
+            writer: compound.writer,
+            premium_lamports: premium,
+        });
+
+        msg!("Compound option expired: {} — premium {} lamports to writer {}",
+             compound.underlying.option_id, premium, compound.writer);
+        Ok(())
+    }
+
+    /// Exercise an option — fan pays the strike for every remaining ticket
+    /// and converts the option to tickets (status → Exercised).
+    /// The venue takes its royalty off the strike; the writer gets the rest.
+    /// max_total_cost caps what the holder will pay in total — the royalty
+    /// is carved out of the strike, so that's the strike for every ticket,
+    /// plus config.late_exercise_penalty_bps of it when exercising after
+    /// expiry inside the grace window (it pays out with the strike on confirm).
+    /// In a real system, this would trigger ticket issuance via the venue API.
+    /// KYD: this is the CPI you'd implement on your end. Call us.
+    /// All three exercise instructions hand an ExerciseResult back through
+    /// set_return_data, so a program calling in by CPI can read the outcome
+    /// with get_return_data instead of parsing logs.
+    pub fn exercise_option(ctx: Context<ExerciseOption>, max_total_cost: u64) -> Result<ExerciseResult> {
+        let exercise_qty = ctx.accounts.option_contract.quantity;
+        process_exercise(ctx, exercise_qty, max_total_cost, None, None)
+    }
+
+    /// Exercise every remaining ticket and have the venue issue them to
+    /// someone else — a reseller who already found a buyer settles in one go.
+    /// The beneficiary lands on the ExerciseReceipt; the signer still pays.
+    pub fn exercise_to(
+        ctx: Context<ExerciseOption>,
+        beneficiary: Pubkey,
+        max_total_cost: u64,
+    ) -> Result<ExerciseResult> {
+        require_keys_neq!(
+            beneficiary,
+            ctx.accounts.option_contract.holder,
+            QuorumError::InvalidRecipient
+        );
+        let exercise_qty = ctx.accounts.option_contract.quantity;
+        process_exercise(ctx, exercise_qty, max_total_cost, Some(beneficiary), None)
+    }
+
+    /// Exercise some of the tickets on a multi-ticket option.
+    /// The rest stay live; status only flips to Exercised once none remain.
+    pub fn exercise_partial(
+        ctx: Context<ExerciseOption>,
+        exercise_qty: u8,
+        max_total_cost: u64,
+    ) -> Result<ExerciseResult> {
+        process_exercise(ctx, exercise_qty, max_total_cost, None, None)
+    }
+
+    /// Gasless exercise: a relayer signs and pays the transaction — fees,
+    /// receipt rent and the strike, which it recovers from the fan off-chain
+    /// — while the fan only signs `authorization` off-chain. The transaction
+    /// carries an ed25519 program instruction verifying that signature
+    /// immediately before this one; the signing key must be able to exercise
+    /// the option as in exercise_partial (holder, delegate, co-holder or NFT
+    /// owner). Tickets go to the fan; a failed issuance refunds the relayer.
+    /// Each nonce works once per fan.
+    /// remaining_accounts: [writable QuoteNonce PDA at [b"relay_nonce",
+    /// authorization.holder, authorization.nonce], instructions sysvar].
+    pub fn exercise_option_relayed(
+        ctx: Context<ExerciseOption>,
+        authorization: RelayedExercise,
+    ) -> Result<ExerciseResult> {
+        require!(ctx.remaining_accounts.len() >= 2, QuorumError::InvalidRelayAuthorization);
+        let option = &ctx.accounts.option_contract;
+        require!(
+            authorization.option_id == option.option_id
+                && authorization.client_nonce == option.client_nonce,
+            QuorumError::InvalidRelayAuthorization
+        );
+        let now = current_timestamp(&ctx.accounts.clock)?;
+        require!(now <= authorization.valid_until, QuorumError::AuthorizationExpired);
+
+        verify_ed25519_signature(
+            &ctx.remaining_accounts[1],
+            &authorization.holder,
+            &authorization.try_to_vec()?,
+        )?;
+        consume_nonce(
+            &ctx.remaining_accounts[0],
+            b"relay_nonce",
+            authorization.holder,
+            authorization.nonce,
+            &ctx.accounts.holder.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            ctx.program_id,
+            now,
+        )?;
+
+        process_exercise(
+            ctx,
+            authorization.exercise_qty,
+            authorization.max_total_cost,
+            None,
+            Some(authorization.holder),
+        )
+    }
+
+    /// Exercise several options in one transaction — a reseller fulfilling
+    /// one buyer from many positions. remaining_accounts are (option,
+    /// holder_stats, receipt, event_registry) quads, one per entry in
+    /// `quantities` (tickets to exercise on that option; all of them flips it
+    /// to pending issuance). Only plain SOL-strike options the signer holds or
+    /// is delegate on qualify — NFT-backed, co-held, pooled and token-strike
+    /// options go through exercise_option. Each entry is checked as in
+    /// exercise_partial and any failure rolls back the whole batch;
+    /// max_total_cost caps the strike across all of them.
+    /// Returns the total ticket count exercised.
+    pub fn exercise_options_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ExerciseOptionsBatch<'info>>,
+        quantities: Vec<u8>,
+        max_total_cost: u64,
+    ) -> Result<u32> {
+        require!(
+            !quantities.is_empty() && quantities.len() <= MAX_BATCH_SIZE,
+            QuorumError::InvalidBatchSize
+        );
+        require!(
+            quantities.len() * 4 == ctx.remaining_accounts.len(),
+            QuorumError::BatchAccountsMismatch
+        );
+
+        let now = current_timestamp(&ctx.accounts.clock)?;
+        let signer = ctx.accounts.holder.key();
+        let mut total_cost: u64 = 0;
+        let mut total_tickets: u32 = 0;
+
+        for (&exercise_qty, entry) in quantities.iter().zip(ctx.remaining_accounts.chunks(4)) {
+            let (option_info, stats_info, receipt_info, registry_info) =
+                (&entry[0], &entry[1], &entry[2], &entry[3]);
+            let mut option: Account<'info, OptionContract> = Account::try_from(option_info)?;
+
+            require!(option.status_enum()? == OptionStatus::Active, QuorumError::NotActive);
+            require!(!option.frozen, QuorumError::OptionFrozen);
+            require_not_nft_backed(&option)?;
+            require!(option.co_holders.is_empty(), QuorumError::CoHeld);
+            require!(option.pool.is_none(), QuorumError::InvalidPool);
+            require!(option.strike_mint.is_none(), QuorumError::MissingTokenAccounts);
+            require!(option.identity_hash.is_none(), QuorumError::IdentityUnsupported);
+            require!(
+                signer == option.holder || Some(signer) == option.delegate,
+                QuorumError::UnauthorizedHolder
+            );
+            require!(
+                exercise_qty > 0 && exercise_qty <= option.quantity,
+                QuorumError::InvalidExerciseQuantity
+            );
+            require!(
+                now <= exercise_deadline(&option, &ctx.accounts.config),
+                QuorumError::OptionExpired
+            );
+            require!(
+                now >= option.exercise_window_start,
+                QuorumError::TooEarlyToExercise
+            );
+
+            let strike_paid = option
+                .strike_lamports
+                .checked_mul(exercise_qty as u64)
+                .ok_or(QuorumError::MathOverflow)?;
+            let registry = load_event_registry(registry_info, option.writer, &option.event_name, ctx.program_id)?;
+            if exercise_qty != option.quantity {
+                require_lot_multiple(registry.as_ref(), exercise_qty)?;
+            }
+            let royalty_bps = effective_royalty_bps(registry.as_ref(), &option);
+            let royalty = (strike_paid as u128 * royalty_bps as u128 / 10_000) as u64;
+            let fee = exercise_fee(&ctx.accounts.config, strike_paid);
+            let penalty = late_exercise_penalty(&ctx.accounts.config, &option, strike_paid, now);
+            let owed = strike_paid.checked_add(penalty).ok_or(QuorumError::MathOverflow)?;
+            total_cost = total_cost.checked_add(owed).ok_or(QuorumError::MathOverflow)?;
+            require!(total_cost <= max_total_cost, QuorumError::SlippageExceeded);
+
+            let mut stats: Account<'info, HolderStats> = Account::try_from(stats_info)?;
+            require_keys_eq!(stats.holder, option.holder, QuorumError::BatchAccountsMismatch);
+
+            // Effects first, as in process_exercise
+            option.quantity -= exercise_qty;
+            option.exercised_qty += exercise_qty;
+            if option.quantity == 0 {
+                option.set_status(OptionStatus::PendingIssuance)?;
+                remove_active_option(&mut stats);
+                ctx.accounts.global_stats.remove_active(1);
+            }
+            let prefunded = option.strike_escrowed > 0;
+            if prefunded {
+                option.strike_escrowed = option
+                    .strike_escrowed
+                    .checked_sub(strike_paid)
+                    .ok_or(QuorumError::MathOverflow)?;
+            }
+            let issuance_deadline = now
+                .checked_add(ctx.accounts.config.issuance_timeout_seconds as i64)
+                .ok_or(QuorumError::MathOverflow)?;
+            let exerciser = option.holder;
+            let payer = if prefunded { exerciser } else { signer };
+            hold_for_issuance(&mut option, payer, None, prefunded, owed, royalty, fee, exercise_qty, issuance_deadline)?;
+
+            // The receipt PDA is created on the option's first exercise
+            let nonce = option.client_nonce.to_le_bytes();
+            let (expected, receipt_bump) =
+                Pubkey::find_program_address(&[b"receipt", option.option_id.as_bytes(), &nonce], ctx.program_id);
+            require_keys_eq!(receipt_info.key(), expected, QuorumError::BatchAccountsMismatch);
+            let mut receipt = if receipt_info.data_is_empty() {
+                let seeds: &[&[u8]] = &[b"receipt", option.option_id.as_bytes(), &nonce, &[receipt_bump]];
+                create_pda_account(
+                    receipt_info,
+                    &ctx.accounts.holder.to_account_info(),
+                    &ctx.accounts.system_program.to_account_info(),
+                    ExerciseReceipt::MAX_SIZE,
+                    seeds,
+                    ctx.program_id,
+                )?;
+                ExerciseReceipt::default()
+            } else {
+                require_keys_eq!(*receipt_info.owner, *ctx.program_id, QuorumError::BatchAccountsMismatch);
+                let data = receipt_info.try_borrow_data()?;
+                ExerciseReceipt::try_deserialize(&mut &data[..])?
+            };
+            write_receipt(
+                &mut receipt,
+                &option,
+                receipt_bump,
+                exerciser,
+                exerciser,
+                exercise_qty,
+                strike_paid,
+                now,
+            )?;
+            {
+                let mut data = receipt_info.try_borrow_mut_data()?;
+                receipt.try_serialize(&mut &mut data[..])?;
+            }
+            // Persist now, so a duplicate later in the batch sees what's left
+            option.exit(ctx.program_id)?;
+            stats.exit(ctx.program_id)?;
+
+            if !prefunded && owed > 0 {
+                system_program::transfer(
+                    CpiContext::new(
+                        ctx.accounts.system_program.to_account_info(),
+                        system_program::Transfer {
+                            from: ctx.accounts.holder.to_account_info(),
+                            to: option_info.clone(),
+                        },
+                    ),
+                    owed,
+                )?;
+                emit_lamport_flow(&option.option_id, FlowKind::StrikeEscrow, owed, signer, option.key());
+            }
+            emit_exercised(&option, strike_paid, exercise_qty, now);
+            emit_late_exercise_penalty(&option, penalty);
+            total_tickets += exercise_qty as u32;
+        }
+
+        msg!("Batch exercise: {} tickets across {} options by {} — strike: {} lamports",
+             total_tickets, quantities.len(), signer, total_cost);
+        Ok(total_tickets)
+    }
+
+    /// Create the GlobalStats singleton. Every lifecycle instruction takes
+    /// it, so this runs once per deployment before the first option.
+    pub fn initialize_global_stats(ctx: Context<InitializeGlobalStats>) -> Result<()> {
+        ctx.accounts.global_stats.bump = ctx.bumps.global_stats;
+        Ok(())
+    }
+
+    /// Top up the KeeperTreasury that pays expiry rewards. Anyone can fund
+    /// it; the first call creates the PDA.
+    pub fn fund_keeper_treasury(ctx: Context<FundKeeperTreasury>, amount: u64) -> Result<()> {
+        require!(amount > 0, QuorumError::InvalidFundingAmount);
+
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.funder.to_account_info(),
+                    to: ctx.accounts.keeper_treasury.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        let treasury = &mut ctx.accounts.keeper_treasury;
+        treasury.bump = ctx.bumps.keeper_treasury;
+        treasury.balance = treasury.balance.checked_add(amount).ok_or(QuorumError::MathOverflow)?;
+
+        emit!(KeeperTreasuryFunded {
+            funder: ctx.accounts.funder.key(),
+            amount,
+            balance: treasury.balance,
+        });
+        msg!("Keeper treasury funded: {} lamports — balance: {}", amount, treasury.balance);
+        Ok(())
+    }
+
+    /// Expire an option — anyone can call this after expiry timestamp.
+    /// The caller earns config.expire_reward_lamports from the KeeperTreasury
+    /// when one is passed and can cover it; otherwise the expiry goes through
+    /// unpaid. The whole premium stays in the PDA until the writer withdraws it.
+    /// This is how venues capture upside from options they write.
+    pub fn expire_option(ctx: Context<ExpireOption>) -> Result<()> {
+        let option = &mut ctx.accounts.option_contract;
+
+        require!(option.status_enum()? == OptionStatus::Active, QuorumError::NotActive);
+        // Its prefunded strike must never sit behind a writer sweep
+        require!(!option.auto_exercise, QuorumError::AutoExercise);
+        // A frozen option's premium stays put until the freeze lifts
+        require!(!option.frozen, QuorumError::OptionFrozen);
+
+        let now = current_timestamp(&ctx.accounts.clock)?;
+        require!(
+            now > exercise_deadline(option, &ctx.accounts.config),
+            QuorumError::NotExpiredYet
+        );
+
+        settle_expiry(option, &mut ctx.accounts.holder_stats, &mut ctx.accounts.global_stats)?;
+
+        let option = &ctx.accounts.option_contract;
+        let keeper_reward = pay_keeper_reward(
+            ctx.accounts.keeper_treasury.as_mut(),
+            &ctx.accounts.caller.to_account_info(),
+            &ctx.accounts.config,
+            &option.option_id,
+        )?;
+        record_event_stats(
+            &ctx.accounts.event_stats,
+            &option.event_name,
+            &ctx.accounts.caller,
+            &ctx.accounts.system_program,
+            ctx.program_id,
+            |stats| stats.total_expired += 1,
+        )?;
+        emit_option_expired(option, ctx.accounts.caller.key(), keeper_reward, now);
+
+        // SPL premiums stay in the escrow token account, SOL premiums in the PDA
+        match option.premium_mint {
+            Some(mint) => msg!("Option expired: {} — premium retained: {} of mint {}",
+                               option.option_id, option.premium_lamports, mint),
+            None => msg!("Option expired: {} — premium retained: {} lamports",
+                         option.option_id, option.premium_lamports),
+        }
+        Ok(())
+    }
+
+    /// Same as expire_option, but an option that's already Expired is a
+    /// no-op success instead of NotActive, so racing keepers don't fail.
+    /// Nothing is emitted or paid on the no-op path.
+    pub fn expire_option_idempotent(ctx: Context<ExpireOption>) -> Result<()> {
+        if ctx.accounts.option_contract.status_enum()? == OptionStatus::Expired {
+            msg!("Option already expired: {}", ctx.accounts.option_contract.option_id);
+            return Ok(());
+        }
+        expire_option(ctx)
+    }
+
+    /// Keeper settles an auto-exercise option once expiry passes. Unless the
+    /// writer marked the event cancelled, the prefunded strike is held for
+    /// issuance exactly as a full exercise would be and a receipt is written,
+    /// so a fan who forgot to exercise still gets their tickets.
+    pub fn settle_at_expiry(ctx: Context<SettleAtExpiry>) -> Result<()> {
+        let option = &ctx.accounts.option_contract;
+        require!(option.status_enum()? == OptionStatus::Active, QuorumError::NotActive);
+        require!(option.auto_exercise, QuorumError::NotAutoExercise);
+        require!(!option.frozen, QuorumError::OptionFrozen);
+
+        let now = current_timestamp(&ctx.accounts.clock)?;
+        require!(now > option.expiry, QuorumError::NotExpiredYet);
+
+        let registry = load_event_registry(
+            &ctx.accounts.event_registry,
+            option.writer,
+            &option.event_name,
+            ctx.program_id,
+        )?;
+        // Nothing to deliver for a cancelled show — the holder claims a refund instead
+        require!(
+            !registry.as_ref().is_some_and(|registry| registry.cancelled),
+            QuorumError::EventWasCancelled
+        );
+        let royalty_bps = effective_royalty_bps(registry.as_ref(), option);
+
+        let quantity = option.quantity;
+        let strike_paid = option.strike_escrowed;
+        let royalty = (strike_paid as u128 * royalty_bps as u128 / 10_000) as u64;
+        let fee = exercise_fee(&ctx.accounts.config, strike_paid);
+        let issuance_deadline = now
+            .checked_add(ctx.accounts.config.issuance_timeout_seconds as i64)
+            .ok_or(QuorumError::MathOverflow)?;
+        // The auto-exercise draws on a pool's tickets like any other exercise
+        count_pool_exercise(&mut ctx.accounts.pool, option, quantity)?;
+
+        // No lamports move here: the prefunded strike now waits on the venue
+        let option = &mut ctx.accounts.option_contract;
+        option.quantity = 0;
+        option.exercised_qty = option.exercised_qty.checked_add(quantity).ok_or(QuorumError::MathOverflow)?;
+        option.strike_escrowed = 0;
+        option.set_status(OptionStatus::PendingIssuance)?;
+        for co_holder in option.co_holders.iter_mut() {
+            co_holder.shares = 0;
+        }
+        remove_active_option(&mut ctx.accounts.holder_stats);
+        ctx.accounts.global_stats.remove_active(1);
+
+        let holder = option.holder;
+        hold_for_issuance(option, holder, None, true, strike_paid, royalty, fee, quantity, issuance_deadline)?;
+        write_receipt(
+            &mut ctx.accounts.receipt,
+            option,
+            ctx.bumps.receipt,
+            holder,
+            holder,
+            quantity,
+            strike_paid,
+            now,
+        )?;
+
+        let option = &ctx.accounts.option_contract;
+        emit_exercised(option, strike_paid, quantity, now);
+
+        msg!("Option auto-exercised: {} for {} — {} tickets, strike: {} lamports",
+             option.option_id, holder, quantity, strike_paid);
+        Ok(())
+    }
+
+    /// Venue confirms the tickets behind an option's pending exercise were
+    /// actually issued. Only then does the held strike pay out — royalty to
+    /// the venue, the exercise fee to the treasury, the rest to the event's
+    /// settlement account (the writer unless one is set) — and a fully
+    /// exercised option becomes Exercised. The holder's cost is
+    /// the strike either way: royalty + fee + writer share always sum to it.
+    pub fn confirm_issuance(ctx: Context<ConfirmIssuance>) -> Result<()> {
+        let option = &ctx.accounts.option_contract;
+        require!(option.pending_strike > 0, QuorumError::NotPendingIssuance);
+        let registry = load_event_registry(
+            &ctx.accounts.event_registry,
+            option.writer,
+            &option.event_name,
+            ctx.program_id,
+        )?;
+        let payee = settlement_destination(registry.as_ref(), option);
+        require_keys_eq!(
+            ctx.accounts.settlement_account.key(),
+            payee,
+            QuorumError::InvalidSettlementAccount
+        );
+
+        // Effects before interactions
+        let PendingPayout { strike, royalty, fee, writer_share, quantity, fully_exercised } =
+            take_pending_issuance(&mut ctx.accounts.option_contract)?;
+        if fully_exercised {
+            let global = &mut ctx.accounts.global_stats;
+            global.total_exercised = global.total_exercised.saturating_add(1);
+        }
+
+        let option = &ctx.accounts.option_contract;
+        match option.strike_mint {
+            // Token strikes pay out of the strike escrow, signed by the option PDA
+            Some(mint) => {
+                if royalty > 0 {
+                    let (escrow, venue_token, token_program) = unpack_spl_accounts(
+                        &ctx.accounts.strike_escrow,
+                        &ctx.accounts.venue_strike_token,
+                        &ctx.accounts.token_program,
+                    )?;
+                    require_keys_eq!(venue_token.mint, mint, QuorumError::InvalidRecipient);
+                    require_keys_eq!(venue_token.owner, option.venue, QuorumError::InvalidRecipient);
+                    transfer_from_escrow(option, escrow, venue_token, token_program, royalty)?;
+                }
+                if fee > 0 {
+                    let (escrow, treasury_token, token_program) = unpack_spl_accounts(
+                        &ctx.accounts.strike_escrow,
+                        &ctx.accounts.treasury_strike_token,
+                        &ctx.accounts.token_program,
+                    )?;
+                    require_keys_eq!(treasury_token.mint, mint, QuorumError::InvalidRecipient);
+                    require_keys_eq!(treasury_token.owner, ctx.accounts.config.treasury, QuorumError::InvalidTreasury);
+                    transfer_from_escrow(option, escrow, treasury_token, token_program, fee)?;
+                }
+                if writer_share > 0 {
+                    let (escrow, writer_token, token_program) = unpack_spl_accounts(
+                        &ctx.accounts.strike_escrow,
+                        &ctx.accounts.writer_strike_token,
+                        &ctx.accounts.token_program,
+                    )?;
+                    require_keys_eq!(writer_token.mint, mint, QuorumError::InvalidRecipient);
+                    require_keys_eq!(writer_token.owner, payee, QuorumError::InvalidSettlementAccount);
+                    transfer_from_escrow(option, escrow, writer_token, token_program, writer_share)?;
+                }
+            }
+            None => {
+                let option_info = option.to_account_info();
+                transfer_from_pda(&option_info, &ctx.accounts.venue, royalty)?;
+                emit_lamport_flow(&option.option_id, FlowKind::Royalty, royalty, option.key(), option.venue);
+                transfer_from_pda(&option_info, &ctx.accounts.treasury, fee)?;
+                emit_lamport_flow(&option.option_id, FlowKind::Fee, fee, option.key(), ctx.accounts.treasury.key());
+                transfer_from_pda(&option_info, &ctx.accounts.settlement_account, writer_share)?;
+                emit_lamport_flow(&option.option_id, FlowKind::Strike, writer_share, option.key(), payee);
+            }
+        }
+        if royalty > 0 {
+            emit!(RoyaltyPaid {
+                option_id: option.option_id.clone(),
+                venue: option.venue,
+                amount: royalty,
+            });
+        }
+        if fee > 0 {
+            emit!(ExerciseFeePaid {
+                option_id: option.option_id.clone(),
+                treasury: ctx.accounts.config.treasury,
+                amount: fee,
+            });
+        }
+
+        if fully_exercised {
+            record_event_stats(
+                &ctx.accounts.event_stats,
+                &option.event_name,
+                &ctx.accounts.venue,
+                &ctx.accounts.system_program,
+                ctx.program_id,
+                |stats| stats.total_exercised += 1,
+            )?;
+            emit_status_changed(option, OptionStatus::PendingIssuance as u8, current_timestamp(&ctx.accounts.clock)?);
+        }
+        emit!(IssuanceConfirmed {
+            option_id: option.option_id.clone(),
+            venue: option.venue,
+            quantity,
+            strike_lamports: strike,
+        });
+
+        msg!("Issuance confirmed: {} — {} tickets, strike: {} lamports",
+             option.option_id, quantity, strike);
+        Ok(())
+    }
+
+    /// Anyone can unwind a pending exercise the venue never confirmed once
+    /// its issuance deadline passes: the strike goes back to whoever paid it
+    /// and the tickets become exercisable again. A prefunded strike returns
+    /// to the escrow, or to the holder if the option is already past expiry.
+    pub fn refund_failed_issuance(ctx: Context<RefundFailedIssuance>) -> Result<()> {
+        let option = &ctx.accounts.option_contract;
+        require!(option.pending_strike > 0, QuorumError::NotPendingIssuance);
+        let now = current_timestamp(&ctx.accounts.clock)?;
+        require!(
+            now > option.issuance_deadline,
+            QuorumError::IssuanceTimeoutNotReached
+        );
+
+        let strike = option.pending_strike;
+        let quantity = option.pending_qty;
+        let payer = option.pending_payer;
+        let exercised_for = option.pending_co_holder;
+        let from_escrow = option.pending_from_escrow;
+        let previous_status = option.status_enum()?;
+
+        // Roll the exercise back before any lamports move
+        if let Some(pool_key) = option.pool {
+            let pool = ctx.accounts.pool.as_mut().ok_or(QuorumError::InvalidPool)?;
+            require_keys_eq!(pool.key(), pool_key, QuorumError::InvalidPool);
+            pool.exercised_count = pool.exercised_count.saturating_sub(quantity as u32);
+        }
+        let receipt = &mut ctx.accounts.receipt;
+        receipt.quantity = receipt.quantity.saturating_sub(quantity);
+        receipt.strike_paid = receipt.strike_paid.saturating_sub(strike);
+
+        let option = &mut ctx.accounts.option_contract;
+        clear_pending_issuance(option);
+        option.quantity = option.quantity.checked_add(quantity).ok_or(QuorumError::MathOverflow)?;
+        option.exercised_qty = option.exercised_qty.saturating_sub(quantity);
+        // Shares go back to the co-holder who exercised, not whoever paid
+        if let Some(co_holder) = option.co_holders.iter_mut().find(|c| Some(c.holder) == exercised_for) {
+            co_holder.shares = co_holder.shares.checked_add(quantity).ok_or(QuorumError::MathOverflow)?;
+        }
+        if previous_status == OptionStatus::PendingIssuance {
+            option.set_status(OptionStatus::Active)?;
+            let stats = &mut ctx.accounts.holder_stats;
+            stats.active_count = stats.active_count.saturating_add(1);
+            ctx.accounts.global_stats.add_active(1);
+        }
+
+        if from_escrow {
+            option.strike_escrowed = option
+                .strike_escrowed
+                .checked_add(strike)
+                .ok_or(QuorumError::MathOverflow)?;
+            // Past expiry an auto-exercise option could only be settled again
+            if now > option.expiry {
+                option.auto_exercise = false;
+                refund_strike_escrow(option, &ctx.accounts.holder)?;
+            }
+        } else if let Some(mint) = option.strike_mint {
+            let (escrow, payer_token, token_program) = unpack_spl_accounts(
+                &ctx.accounts.strike_escrow,
+                &ctx.accounts.payer_strike_token,
+                &ctx.accounts.token_program,
+            )?;
+            require_keys_eq!(payer_token.mint, mint, QuorumError::InvalidRecipient);
+            require_keys_eq!(payer_token.owner, payer, QuorumError::InvalidRecipient);
+            transfer_from_escrow(option, escrow, payer_token, token_program, strike)?;
+        } else {
+            transfer_from_pda(&option.to_account_info(), &ctx.accounts.payer, strike)?;
+            emit_lamport_flow(&option.option_id, FlowKind::Refund, strike, option.key(), payer);
+        }
+
+        let option = &ctx.accounts.option_contract;
+        if option.status != previous_status {
+            emit_status_changed(option, previous_status, now);
+        }
+        emit!(IssuanceRefunded {
+            option_id: option.option_id.clone(),
+            payer,
+            quantity,
+            strike_lamports: strike,
+        });
+
+        msg!("Issuance refunded: {} — {} tickets, {} lamports back to {}",
+             option.option_id, quantity, strike, payer);
+        Ok(())
+    }
+
+    /// Permissionless reminder crank: once an Active option is within
+    /// config.expiry_warning_seconds of expiry, emit one ExpiryWarning for
+    /// notification bots to nudge the holder. Fails outside the window or
+    /// if this expiry was already warned about.
+    pub fn ping_expiry_warning(ctx: Context<PingExpiryWarning>) -> Result<()> {
+        let option = &ctx.accounts.option_contract;
+        require!(option.status_enum()? == OptionStatus::Active, QuorumError::NotActive);
+        require!(!option.warned, QuorumError::AlreadyWarned);
+
+        let now = current_timestamp(&ctx.accounts.clock)?;
+        require!(now <= option.expiry, QuorumError::OptionExpired);
+        let seconds_left = option.expiry - now;
+        require!(
+            seconds_left <= ctx.accounts.config.expiry_warning_seconds as i64,
+            QuorumError::NotInWarningWindow
+        );
+
+        let option = &mut ctx.accounts.option_contract;
+        option.warned = true;
+
+        emit!(ExpiryWarning {
+            option_id: option.option_id.clone(),
+            holder: option.holder,
+            seconds_left,
+        });
+
+        msg!("Expiry warning: {} — {}s left for {}", option.option_id, seconds_left, option.holder);
+        Ok(())
+    }
+
+    /// Authority or writer freezes one contested option: no exercise,
+    /// transfer, cancel or expiry, and no writer payout, until unfrozen.
+    /// The surgical alternative to pausing the whole program.
+    pub fn freeze_option(ctx: Context<FreezeOption>) -> Result<()> {
+        set_option_frozen(ctx, true)
+    }
+
+    /// Lift a freeze_option.
+    pub fn unfreeze_option(ctx: Context<FreezeOption>) -> Result<()> {
+        set_option_frozen(ctx, false)
+    }
+
+    /// Keeper sweep: expire many options in one call. remaining_accounts are
+    /// (option, holder_stats, event_stats) triples; options that aren't Active, aren't
+    /// past expiry yet or are auto-exercise are skipped rather than failing the batch.
+    /// Returns how many were expired; rewards are paid from the KeeperTreasury
+    /// as in expire_option, until it runs dry.
+    pub fn expire_options_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ExpireOptionsBatch<'info>>,
+    ) -> Result<u32> {
+        let entries = ctx.remaining_accounts.len() / 3;
+        require!(
+            ctx.remaining_accounts.len() % 3 == 0,
+            QuorumError::BatchAccountsMismatch
+        );
+        require!(entries > 0 && entries <= MAX_BATCH_SIZE, QuorumError::InvalidBatchSize);
+
+        let now = current_timestamp(&ctx.accounts.clock)?;
+        let caller = ctx.accounts.caller.to_account_info();
+        let mut expired: u32 = 0;
+
+        for entry in ctx.remaining_accounts.chunks(3) {
+            let mut option: Account<'info, OptionContract> = Account::try_from(&entry[0])?;
+            if option.status_enum()? != OptionStatus::Active
+                || option.auto_exercise
+                || option.frozen
+                || now <= exercise_deadline(&option, &ctx.accounts.config)
+            {
+                continue;
+            }
+            let mut stats: Account<'info, HolderStats> = Account::try_from(&entry[1])?;
+            require_keys_eq!(stats.holder, option.holder, QuorumError::BatchAccountsMismatch);
+
+            settle_expiry(&mut option, &mut stats, &mut ctx.accounts.global_stats)?;
+            // Persist now, so a duplicate later in the batch reads Expired and is skipped
+            option.exit(ctx.program_id)?;
+            stats.exit(ctx.program_id)?;
+
+            let keeper_reward = pay_keeper_reward(
+                ctx.accounts.keeper_treasury.as_mut(),
+                &caller,
+                &ctx.accounts.config,
+                &option.option_id,
+            )?;
+            record_event_stats(
+                &entry[2],
+                &option.event_name,
+                &caller,
+                &ctx.accounts.system_program,
+                ctx.program_id,
+                |stats| stats.total_expired += 1,
+            )?;
+            emit_option_expired(&option, caller.key(), keeper_reward, now);
+            expired += 1;
+        }
+
+        msg!("Batch expire: {} of {} options expired", expired, entries);
+        Ok(expired)
+    }
+
+    /// Transfer an option to a new holder — the secondary market.
+    /// Only the current holder can sell, and only while the option is live.
+    /// Premium stays put in the PDA; only the right to exercise moves.
+    pub fn transfer_option(ctx: Context<TransferOption>, new_holder: Pubkey) -> Result<()> {
+        let option = &mut ctx.accounts.option_contract;
+
+        require_not_nft_backed(option)?;
+        require!(option.co_holders.is_empty(), QuorumError::CoHeld);
+        require!(option.transferable, QuorumError::NonTransferable);
+
+        require!(option.status_enum()? == OptionStatus::Active, QuorumError::NotActive);
+        require!(!option.frozen, QuorumError::OptionFrozen);
+        require!(
+            ctx.accounts.holder.key() == option.holder,
+            QuorumError::UnauthorizedHolder
+        );
+
+        let now = current_timestamp(&ctx.accounts.clock)?;
+        require!(now <= option.expiry, QuorumError::OptionExpired);
+
+        require_keys_neq!(new_holder, option.holder, QuorumError::InvalidRecipient);
+
+        // An identity-bound option only moves to another verified wallet,
+        // and is bound to the buyer's identity from then on
+        if option.identity_hash.is_some() {
+            let identity = require_identity(ctx.accounts.to_identity.as_deref(), option.venue, new_holder)?;
+            option.identity_hash = Some(identity);
+        }
+
+        let from = option.holder;
+        option.holder = new_holder;
+        // The seller's delegate has no business exercising for the buyer
+        option.delegate = None;
+
+        // The live option moves between holders' counts; the cap applies to the buyer too
+        remove_active_option(&mut ctx.accounts.from_stats);
+        add_active_options(
+            &mut ctx.accounts.to_stats,
+            new_holder,
+            ctx.bumps.to_stats,
+            &ctx.accounts.config,
+            1,
+        )?;
+
+        emit!(OptionTransferred {
+            option_id: option.option_id.clone(),
+            from,
+            to: new_holder,
+        });
+
+        msg!("Option transferred: {} from {} to {}", option.option_id, from, new_holder);
+        Ok(())
+    }
+
+    /// Holder breaks an Active option into smaller ones to sell separately.
+    /// Each leg becomes its own option — same option_id, the leg's
+    /// client_nonce — with every term carried over, parent_option pointing
+    /// back at the original, and its share of the premium by quantity (the
+    /// last leg takes the rounding). The original is closed, its rent back
+    /// to the creator. Pass one uninitialized option PDA per leg in
+    /// remaining_accounts, in the same order. Each extra option counts
+    /// against the event's max_options and the holder's cap.
+    pub fn split_option<'info>(
+        ctx: Context<'_, '_, '_, 'info, SplitOption<'info>>,
+        legs: Vec<SplitLeg>,
+    ) -> Result<()> {
+        require!(!ctx.accounts.config.paused, QuorumError::ProgramPaused);
+        require!(legs.len() >= 2 && legs.len() <= MAX_BATCH_SIZE, QuorumError::InvalidSplit);
+        require!(legs.len() == ctx.remaining_accounts.len(), QuorumError::BatchAccountsMismatch);
+
+        let parent = &ctx.accounts.option_contract;
+        require!(parent.status_enum()? == OptionStatus::Active, QuorumError::NotActive);
+        require!(!parent.frozen, QuorumError::OptionFrozen);
+        require!(
+            ctx.accounts.holder.key() == parent.holder,
+            QuorumError::UnauthorizedHolder
+        );
+        require_not_nft_backed(parent)?;
+        require!(parent.co_holders.is_empty(), QuorumError::CoHeld);
+        // Token escrows and held strikes are keyed to this PDA and can't follow
+        require!(
+            parent.premium_mint.is_none() && parent.strike_mint.is_none(),
+            QuorumError::InvalidSplit
+        );
+        require!(
+            parent.strike_escrowed == 0 && parent.pending_strike == 0,
+            QuorumError::IssuancePending
+        );
+
+        let now = current_timestamp(&ctx.accounts.clock)?;
+        require!(now <= parent.expiry, QuorumError::OptionExpired);
+
+        let total: u32 = legs.iter().map(|leg| leg.quantity as u32).sum();
+        require!(
+            legs.iter().all(|leg| leg.quantity > 0) && total == parent.quantity as u32,
+            QuorumError::InvalidSplit
+        );
+        let registry = load_event_registry(
+            &ctx.accounts.event_registry,
+            parent.writer,
+            &parent.event_name,
+            ctx.program_id,
+        )?;
+        for leg in &legs {
+            require_lot_multiple(registry.as_ref(), leg.quantity)?;
+        }
+        // The original's slot passes to the first leg
+        for _ in 1..legs.len() {
+            take_event_slot(&ctx.accounts.event_registry, parent.writer, &parent.event_name, ctx.program_id)?;
+        }
+        let holder = parent.holder;
+        add_active_options(
+            &mut ctx.accounts.holder_stats,
+            holder,
+            ctx.bumps.holder_stats,
+            &ctx.accounts.config,
+            (legs.len() - 1) as u16,
+        )?;
+        ctx.accounts.global_stats.add_active((legs.len() - 1) as u64);
+
+        let parent = &ctx.accounts.option_contract;
+        let parent_key = parent.key();
+        let parent_info = parent.to_account_info();
+        let mut premium_left = parent.premium_lamports;
+        let mut children = Vec::with_capacity(legs.len());
+
+        for (i, (leg, child_info)) in legs.iter().zip(ctx.remaining_accounts.iter()).enumerate() {
+            let premium = if i + 1 == legs.len() {
+                premium_left
+            } else {
+                (parent.premium_lamports as u128 * leg.quantity as u128 / total as u128) as u64
+            };
+            premium_left = premium_left.checked_sub(premium).ok_or(QuorumError::MathOverflow)?;
+
+            let bump = canonical_option_bump(&parent.option_id, leg.client_nonce, child_info.key(), ctx.program_id)?;
+            require!(child_info.data_is_empty(), QuorumError::OptionIdTaken);
+            let nonce = leg.client_nonce.to_le_bytes();
+            let seeds: &[&[u8]] = &[b"option", OPTION_SEED_VERSION, parent.option_id.as_bytes(), &nonce, &[bump]];
+            create_pda_account(
+                child_info,
+                &ctx.accounts.holder.to_account_info(),
+                &ctx.accounts.system_program.to_account_info(),
+                OptionContract::MAX_SIZE,
+                seeds,
+                ctx.program_id,
+            )?;
+            transfer_from_pda(&parent_info, child_info, premium)?;
+
+            let mut child = OptionContract::clone(parent);
+            child.quantity = leg.quantity;
+            child.premium_lamports = premium;
+            child.client_nonce = leg.client_nonce;
+            child.bump = bump;
+            child.created_at = now;
+            child.exercised_qty = 0;
+            child.parent_option = Some(parent_key);
+            // The holder paid this leg's rent, so closing it pays them back
+            child.creator = ctx.accounts.holder.key();
+            {
+                let mut data = child_info.try_borrow_mut_data()?;
+                child.try_serialize(&mut &mut data[..])?;
+            }
+            index_option(
+                &ctx.accounts.event_index,
+                &parent.event_name,
+                child_info.key(),
+                &ctx.accounts.holder,
+                &ctx.accounts.system_program,
+                ctx.program_id,
+            )?;
+            emit_lamport_flow(&parent.option_id, FlowKind::Premium, premium, parent_key, child_info.key());
+            children.push(child_info.key());
+        }
+        unindex_option(
+            &ctx.accounts.event_index,
+            &parent.event_name,
+            parent_key,
+            &ctx.accounts.holder,
+            ctx.program_id,
+        )?;
+
+        let quantities: Vec<u8> = legs.iter().map(|leg| leg.quantity).collect();
+        emit!(OptionSplit {
+            option_id: parent.option_id.clone(),
+            parent_option: parent_key,
+            holder,
+            children,
+            quantities,
+        });
+
+        msg!("Option split: {} — {} tickets into {} options", parent.option_id, total, legs.len());
+        Ok(())
+    }
+
+    /// Inverse of split_option: holder folds several same-terms options into
+    /// one new option (option_id, client_nonce) with their quantities and
+    /// premiums summed, up to config.max_quantity. Every term but quantity
+    /// and premium must match, or the merge fails with TermsMismatch.
+    /// remaining_accounts are (option, creator) pairs; each original is
+    /// closed with its rent back to its creator, and every option folded
+    /// away frees its slot in the event's max_options and the holder's cap.
+    pub fn merge_options<'info>(
+        ctx: Context<'_, '_, 'info, 'info, MergeOptions<'info>>,
+        option_id: String,
+        client_nonce: u64,
+    ) -> Result<()> {
+        require!(!ctx.accounts.config.paused, QuorumError::ProgramPaused);
+        require!(
+            ctx.accounts.option_contract.created_at == 0,
+            QuorumError::OptionIdTaken
+        );
+        require!(ctx.remaining_accounts.len() % 2 == 0, QuorumError::BatchAccountsMismatch);
+        let count = ctx.remaining_accounts.len() / 2;
+        require!(count >= 2 && count <= MAX_BATCH_SIZE, QuorumError::InvalidMerge);
+        require!(!option_id.is_empty(), QuorumError::EmptyString);
+        require!(
+            option_id.len() <= ctx.accounts.config.max_option_id_len as usize,
+            QuorumError::StringTooLong
+        );
+
+        let now = current_timestamp(&ctx.accounts.clock)?;
+        let holder = ctx.accounts.holder.key();
+        let mut inputs: Vec<Account<'info, OptionContract>> = Vec::with_capacity(count);
+        for pair in ctx.remaining_accounts.chunks(2) {
+            let option: Account<'info, OptionContract> = Account::try_from(&pair[0])?;
+            require!(option.status_enum()? == OptionStatus::Active, QuorumError::NotActive);
+            require!(!option.frozen, QuorumError::OptionFrozen);
+            require_keys_eq!(option.holder, holder, QuorumError::UnauthorizedHolder);
+            require_not_nft_backed(&option)?;
+            require!(option.co_holders.is_empty(), QuorumError::CoHeld);
+            // Token escrows and held strikes are keyed to each PDA and can't follow
+            require!(
+                option.premium_mint.is_none() && option.strike_mint.is_none(),
+                QuorumError::InvalidMerge
+            );
+            require!(
+                option.strike_escrowed == 0 && option.pending_strike == 0,
+                QuorumError::IssuancePending
+            );
+            require!(now <= option.expiry, QuorumError::OptionExpired);
+            require_keys_eq!(pair[1].key(), option.creator, QuorumError::InvalidRecipient);
+            require!(
+                inputs.iter().all(|input| input.key() != option.key()),
+                QuorumError::InvalidMerge
+            );
+            if let Some(first) = inputs.first() {
+                require!(same_terms(first, &option), QuorumError::TermsMismatch);
+            }
+            inputs.push(option);
+        }
+
+        let quantity: u32 = inputs.iter().map(|input| input.quantity as u32).sum();
+        require!(
+            quantity <= ctx.accounts.config.max_quantity as u32,
+            QuorumError::InvalidQuantity
+        );
+        let premium = inputs
+            .iter()
+            .try_fold(0u64, |sum, input| sum.checked_add(input.premium_lamports))
+            .ok_or(QuorumError::MathOverflow)?;
+
+        let bump = canonical_option_bump(
+            &option_id,
+            client_nonce,
+            ctx.accounts.option_contract.key(),
+            ctx.program_id,
+        )?;
+        let merged_info = ctx.accounts.option_contract.to_account_info();
+        let mut merged = OptionContract::clone(&inputs[0]);
+        merged.option_id = option_id;
+        merged.client_nonce = client_nonce;
+        merged.bump = bump;
+        merged.quantity = quantity as u8;
+        merged.premium_lamports = premium;
+        merged.created_at = now;
+        merged.exercised_qty = 0;
+        merged.parent_option = None;
+        // The holder pays the new account's rent, so it comes home to them
+        merged.creator = holder;
+        // Cancel refunds keep decaying from the oldest input, so listing a
+        // fresh option first can't restart the clock on older premiums
+        merged.refund_decay_start = inputs
+            .iter()
+            .map(|input| input.refund_decay_start)
+            .min()
+            .unwrap_or(now);
+
+        let mut merged_from = Vec::with_capacity(count);
+        for (input, pair) in inputs.iter().zip(ctx.remaining_accounts.chunks(2)) {
+            transfer_from_pda(&pair[0], &merged_info, input.premium_lamports)?;
+            emit_lamport_flow(&input.option_id, FlowKind::Premium, input.premium_lamports, input.key(), merged_info.key());
+            unindex_option(
+                &ctx.accounts.event_index,
+                &input.event_name,
+                input.key(),
+                &ctx.accounts.holder,
+                ctx.program_id,
+            )?;
+            close_program_account(&pair[0], &pair[1])?;
+            merged_from.push(input.key());
+        }
+        // One live option stands where `count` did
+        for _ in 1..count {
+            release_event_slot(&ctx.accounts.event_registry, merged.writer, &merged.event_name, ctx.program_id)?;
+            remove_active_option(&mut ctx.accounts.holder_stats);
+        }
+        ctx.accounts.global_stats.remove_active((count - 1) as u64);
+
+        ctx.accounts.option_contract.set_inner(merged);
+        let option = &ctx.accounts.option_contract;
+        index_option(
+            &ctx.accounts.event_index,
+            &option.event_name,
+            option.key(),
+            &ctx.accounts.holder,
+            &ctx.accounts.system_program,
+            ctx.program_id,
+        )?;
+
+        emit!(OptionsMerged {
+            option_id: option.option_id.clone(),
+            holder,
+            merged_from,
+            quantity: option.quantity,
+            premium_lamports: option.premium_lamports,
+        });
+
+        msg!("Options merged: {} — {} options into {} tickets", option.option_id, count, option.quantity);
+        Ok(())
+    }
+
+    /// Writer sells the short side: new_writer takes over the option, and
+    /// with it the strike on exercise and the premium if it expires. The
+    /// new writer's EventRegistry governs the option from then on. Pooled
+    /// options stay with the pool's writer, and a pending issuance must
+    /// settle first so it's clear whose strike it is.
+    pub fn transfer_writer(ctx: Context<TransferWriter>, new_writer: Pubkey) -> Result<()> {
+        let option = &mut ctx.accounts.option_contract;
+
+        require!(option.status_enum()? == OptionStatus::Active, QuorumError::NotActive);
+        require!(!option.frozen, QuorumError::OptionFrozen);
+        require!(option.pool.is_none(), QuorumError::InvalidPool);
+        require!(option.pending_strike == 0, QuorumError::IssuancePending);
+        require_keys_neq!(new_writer, option.writer, QuorumError::InvalidRecipient);
+        require_whitelisted(&ctx.accounts.config, &ctx.accounts.new_writer_entry, new_writer)?;
+
+        let from = option.writer;
+        option.writer = new_writer;
+
+        emit!(WriterTransferred {
+            option_id: option.option_id.clone(),
+            from,
+            to: new_writer,
+        });
+
+        msg!("Writer transferred: {} from {} to {}", option.option_id, from, new_writer);
+        Ok(())
+    }
+
+    /// Tokenize an option: mint a supply-1 SPL token to the holder and treat
+    /// whoever owns it as the holder from then on, so the option can trade
+    /// on any marketplace. Mint authority is dropped right after, so the
+    /// supply can never grow. Metaplex metadata isn't attached here.
+    pub fn mint_option_nft(ctx: Context<MintOptionNft>) -> Result<()> {
+        let option = &ctx.accounts.option_contract;
+
+        require!(option.status_enum()? == OptionStatus::Active, QuorumError::NotActive);
+        require!(
+            ctx.accounts.holder.key() == option.holder,
+            QuorumError::UnauthorizedHolder
+        );
+        require_not_nft_backed(option)?;
+        require!(option.co_holders.is_empty(), QuorumError::CoHeld);
+        // A token trades on any marketplace, out of our reach
+        require!(option.transferable, QuorumError::NonTransferable);
+        require!(option.identity_hash.is_none(), QuorumError::IdentityUnsupported);
+
+        let nft_mint = ctx.accounts.nft_mint.key();
+        ctx.accounts.option_contract.nft_mint = Some(nft_mint);
+        // A delegate set by the minter shouldn't outlive a sale of the NFT
+        ctx.accounts.option_contract.delegate = None;
+
+        let option = &ctx.accounts.option_contract;
+        let nonce = option.client_nonce.to_le_bytes();
+        let seeds: &[&[u8]] = &[b"option", OPTION_SEED_VERSION, option.option_id.as_bytes(), &nonce, &[option.bump]];
+        token::mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                token::MintTo {
+                    mint: ctx.accounts.nft_mint.to_account_info(),
+                    to: ctx.accounts.holder_nft.to_account_info(),
+                    authority: option.to_account_info(),
+                },
+                &[seeds],
+            ),
+            1,
+        )?;
+        token::set_authority(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                token::SetAuthority {
+                    current_authority: option.to_account_info(),
+                    account_or_mint: ctx.accounts.nft_mint.to_account_info(),
+                },
+                &[seeds],
+            ),
+            token::spl_token::instruction::AuthorityType::MintTokens,
+            None,
+        )?;
+
+        emit!(OptionNftMinted {
+            option_id: option.option_id.clone(),
+            mint: nft_mint,
+            holder: option.holder,
+        });
+
+        msg!("Option NFT minted: {} — mint {}", option.option_id, nft_mint);
+        Ok(())
+    }
+
+    /// Writer fixes a typo in the event metadata instead of burning the option.
+    /// Only fields passed as Some change. Locked once any ticket is exercised.
+    pub fn update_metadata(
+        ctx: Context<UpdateMetadata>,
+        event_name: Option<String>,
+        event_date: Option<String>,
+        ticket_type: Option<String>,
+    ) -> Result<()> {
+        let option = &mut ctx.accounts.option_contract;
+
+        require!(
+            matches!(option.status_enum()?, OptionStatus::Active | OptionStatus::Listed),
+            QuorumError::NotActive
+        );
+        require!(option.exercised_qty == 0, QuorumError::AlreadyExercised);
+
+        let event_name = event_name.unwrap_or_else(|| option.event_name.clone());
+        let event_date = event_date.unwrap_or_else(|| option.event_date.clone());
+        let ticket_type = ticket_type.unwrap_or_else(|| option.ticket_type.clone());
+        validate_metadata(&ctx.accounts.config, &event_name, &event_date, &ticket_type)?;
+        require_event_upcoming(
+            &ctx.accounts.config,
+            &event_date,
+            option.expiry,
+            current_timestamp(&ctx.accounts.clock)?,
+        )?;
+        require_known_ticket_type(
+            &ctx.accounts.event_registry,
+            option.writer,
+            &event_name,
+            &ticket_type,
+            ctx.program_id,
+        )?;
+
+        // A rename moves the option to the new event's index — unless it
+        // only touched case or spacing, which key to the same one
+        if event_seed(&event_name) != event_seed(&option.event_name) {
+            let option_key = option.key();
+            unindex_option(
+                &ctx.accounts.old_event_index,
+                &option.event_name,
+                option_key,
+                &ctx.accounts.writer,
+                ctx.program_id,
+            )?;
+            index_option(
+                &ctx.accounts.new_event_index,
+                &event_name,
+                option_key,
+                &ctx.accounts.writer,
+                &ctx.accounts.system_program,
+                ctx.program_id,
+            )?;
+        }
+
+        option.event_name = event_name;
+        option.event_date = event_date;
+        option.ticket_type = ticket_type;
+
+        emit!(MetadataUpdated {
+            option_id: option.option_id.clone(),
+            event_name: option.event_name.clone(),
+            event_date: option.event_date.clone(),
+            ticket_type: option.ticket_type.clone(),
+        });
+
+        msg!("Metadata updated: {} — {} on {} ({})",
+             option.option_id, option.event_name, option.event_date, option.ticket_type);
+        Ok(())
+    }
+
+    /// Writer points the option at new off-chain display JSON, or clears it
+    /// with an empty string. Unlike update_metadata this is cosmetic, so it
+    /// stays open after partial exercise — but only while Active.
+    pub fn set_metadata_uri(ctx: Context<SetMetadataUri>, metadata_uri: String) -> Result<()> {
+        let option = &mut ctx.accounts.option_contract;
+
+        require!(option.status_enum()? == OptionStatus::Active, QuorumError::NotActive);
+        require!(metadata_uri.len() <= MAX_METADATA_URI_LEN as usize, QuorumError::StringTooLong);
+
+        option.metadata_uri = metadata_uri;
+
+        emit!(MetadataUriUpdated {
+            option_id: option.option_id.clone(),
+            metadata_uri: option.metadata_uri.clone(),
+        });
+        msg!("Metadata URI updated: {} — {}", option.option_id, option.metadata_uri);
+        Ok(())
+    }
+
+    /// Post a standing bid on any option for an event. The bid's lamports
+    /// are escrowed in the event's MarketBook until it's filled or pulled.
+    /// event_hash is event_seed(event_name) — sha256 of the normalized event name.
+    pub fn place_bid(
+        ctx: Context<PlaceBid>,
+        event_hash: [u8; 32],
+        price_lamports: u64,
+        min_quantity: u8,
+    ) -> Result<()> {
+        require!(price_lamports > 0, QuorumError::InvalidPremium);
+        require!(min_quantity > 0, QuorumError::InvalidQuantity);
+
+        let book = &mut ctx.accounts.book;
+        if book.event_hash == [0u8; 32] {
+            book.event_hash = event_hash;
+            book.bump = ctx.bumps.book;
+        }
+        require!(book.bids.len() < MAX_BOOK_BIDS, QuorumError::BookFull);
+
+        let bidder = ctx.accounts.bidder.key();
+        let bid_id = book.next_bid_id;
+        book.next_bid_id += 1;
+        book.bids.push(Bid {
+            bid_id,
+            bidder,
+            price_lamports,
+            min_quantity,
+        });
+
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.bidder.to_account_info(),
+                    to: ctx.accounts.book.to_account_info(),
+                },
+            ),
+            price_lamports,
+        )?;
+        emit_lamport_flow("", FlowKind::BidEscrow, price_lamports, bidder, ctx.accounts.book.key());
+
+        emit!(BidPlaced {
+            book: ctx.accounts.book.key(),
+            bid_id,
+            bidder,
+            price_lamports,
+            min_quantity,
+        });
+
+        msg!("Bid placed: #{} by {} — {} lamports for {}+ tickets",
+             bid_id, bidder, price_lamports, min_quantity);
+        Ok(())
+    }
+
+    /// Holder sells into a standing bid: the option moves to the bidder and
+    /// the escrowed bid moves to the holder in the same instruction.
+    pub fn fill_bid(ctx: Context<FillBid>, bid_id: u64) -> Result<()> {
+        let option = &ctx.accounts.option_contract;
+
+        require!(option.status_enum()? == OptionStatus::Active, QuorumError::NotActive);
+        require!(
+            ctx.accounts.holder.key() == option.holder,
+            QuorumError::UnauthorizedHolder
+        );
+        require_not_nft_backed(option)?;
+        require!(option.co_holders.is_empty(), QuorumError::CoHeld);
+        require!(option.transferable, QuorumError::NonTransferable);
+        require!(!option.frozen, QuorumError::OptionFrozen);
+
+        let now = current_timestamp(&ctx.accounts.clock)?;
+        require!(now <= option.expiry, QuorumError::OptionExpired);
+        require!(
+            event_seed(&option.event_name) == ctx.accounts.book.event_hash,
+            QuorumError::WrongEvent
+        );
+
+        let book = &mut ctx.accounts.book;
+        let index = book
+            .bids
+            .iter()
+            .position(|bid| bid.bid_id == bid_id)
+            .ok_or(QuorumError::BidNotFound)?;
+        let bid = book.bids[index].clone();
+        require_keys_eq!(ctx.accounts.bidder.key(), bid.bidder, QuorumError::InvalidRecipient);
+        require_keys_neq!(bid.bidder, option.holder, QuorumError::InvalidRecipient);
+        require!(option.quantity >= bid.min_quantity, QuorumError::InvalidQuantity);
+
+        // Effects before interactions: bid consumed and option reassigned first
+        book.bids.swap_remove(index);
+        let option = &mut ctx.accounts.option_contract;
+        if option.identity_hash.is_some() {
+            let identity = require_identity(ctx.accounts.bidder_identity.as_deref(), option.venue, bid.bidder)?;
+            option.identity_hash = Some(identity);
+        }
+        let seller = option.holder;
+        option.holder = bid.bidder;
+        option.delegate = None;
+
+        remove_active_option(&mut ctx.accounts.from_stats);
+        add_active_options(
+            &mut ctx.accounts.to_stats,
+            bid.bidder,
+            ctx.bumps.to_stats,
+            &ctx.accounts.config,
+            1,
+        )?;
+
+        transfer_from_pda(
+            &ctx.accounts.book.to_account_info(),
+            &ctx.accounts.holder.to_account_info(),
+            bid.price_lamports,
+        )?;
+
+        let option = &ctx.accounts.option_contract;
+        emit_lamport_flow(
+            &option.option_id,
+            FlowKind::Sale,
+            bid.price_lamports,
+            ctx.accounts.book.key(),
+            seller,
+        );
+        emit!(BidFilled {
+            book: ctx.accounts.book.key(),
+            bid_id,
+            option_id: option.option_id.clone(),
+            seller,
+            buyer: bid.bidder,
+            price_lamports: bid.price_lamports,
+        });
+        emit!(OptionTransferred {
+            option_id: option.option_id.clone(),
+            from: seller,
+            to: bid.bidder,
+        });
+
+        msg!("Bid filled: #{} — {} sold by {} to {} for {} lamports",
+             bid_id, option.option_id, seller, bid.bidder, bid.price_lamports);
+        Ok(())
+    }
+
+    /// Bidder pulls an unfilled bid and gets the escrowed lamports back.
+    pub fn cancel_bid(ctx: Context<CancelBid>, bid_id: u64) -> Result<()> {
+        let book = &mut ctx.accounts.book;
+        let index = book
+            .bids
+            .iter()
+            .position(|bid| bid.bid_id == bid_id)
+            .ok_or(QuorumError::BidNotFound)?;
+        let bid = book.bids[index].clone();
+        require_keys_eq!(ctx.accounts.bidder.key(), bid.bidder, QuorumError::UnauthorizedHolder);
+
+        book.bids.swap_remove(index);
+        transfer_from_pda(
+            &ctx.accounts.book.to_account_info(),
+            &ctx.accounts.bidder.to_account_info(),
+            bid.price_lamports,
+        )?;
+        emit_lamport_flow(
+            "",
+            FlowKind::Refund,
+            bid.price_lamports,
+            ctx.accounts.book.key(),
+            bid.bidder,
+        );
+
+        emit!(BidCancelled {
+            book: ctx.accounts.book.key(),
+            bid_id,
+            bidder: bid.bidder,
+            refund: bid.price_lamports,
+        });
+
+        msg!("Bid cancelled: #{} — {} lamports back to {}", bid_id, bid.price_lamports, bid.bidder);
+        Ok(())
+    }
+
+    /// Let a trusted wallet (custodian, bot) exercise on the holder's behalf.
+    /// Pass None to revoke. The delegate pays the strike when it exercises.
+    pub fn set_delegate(ctx: Context<SetDelegate>, delegate: Option<Pubkey>) -> Result<()> {
+        let option = &mut ctx.accounts.option_contract;
+
+        require_not_nft_backed(option)?;
+
+        require!(option.status_enum()? == OptionStatus::Active, QuorumError::NotActive);
+        require!(
+            ctx.accounts.holder.key() == option.holder,
+            QuorumError::UnauthorizedHolder
+        );
+
+        option.delegate = delegate;
+
+        emit!(DelegateSet {
+            option_id: option.option_id.clone(),
+            holder: option.holder,
+            delegate,
+        });
+
+        match delegate {
+            Some(delegate) => msg!("Delegate set: {} — {} may exercise", option.option_id, delegate),
+            None => msg!("Delegate revoked: {}", option.option_id),
+        }
+        Ok(())
+    }
+
+    /// Read-only health check so clients don't re-implement the timing rules.
+    /// Anchor hands the result back through set_return_data — simulate the
+    /// transaction and decode an OptionState.
+    pub fn get_option_state(ctx: Context<GetOptionState>) -> Result<OptionState> {
+        let option = &ctx.accounts.option_contract;
+        let now = current_timestamp(&ctx.accounts.clock)?;
+
+        let is_active = option.status_enum()? == OptionStatus::Active;
+        let is_expired = now > option.expiry;
+
+        Ok(OptionState {
+            is_active,
+            is_exercisable_now: is_active
+                && !is_expired
+                && now >= option.exercise_window_start
+                && option.quantity > 0,
+            seconds_to_expiry: (option.expiry - now).max(0),
+            is_expired,
+        })
+    }
+
+    /// Read an event's demand score (see EventStats::demand_score) through
+    /// return data, like get_option_state.
+    pub fn get_demand_score(ctx: Context<GetDemandScore>) -> Result<u64> {
+        Ok(ctx.accounts.event_stats.demand_score)
+    }
+
+    /// Read the program-wide GlobalStats counters through return data.
+    pub fn get_global_stats(ctx: Context<GetGlobalStats>) -> Result<GlobalStats> {
+        Ok((*ctx.accounts.global_stats).clone())
+    }
+
+    /// Holder moves a pre-versioning option (seeded without a version byte)
+    /// to its versioned PDA: the state and every lamport it held carry over,
+    /// the event index points at the new address, and the legacy account is
+    /// closed with its rent back to the holder, who paid it. The legacy
+    /// layout is mapped field by field in LegacyOptionContractV0::into_current.
+    pub fn migrate_option(ctx: Context<MigrateOption>, option_id: String) -> Result<()> {
+        let legacy_info = ctx.accounts.legacy_option.to_account_info();
+        require_keys_eq!(*legacy_info.owner, *ctx.program_id, QuorumError::InvalidOptionAccount);
+        let legacy = {
+            let data = legacy_info.try_borrow_data()?;
+            require!(
+                data.len() >= 8 && data[..8] == OptionContract::DISCRIMINATOR[..],
+                QuorumError::InvalidOptionAccount
+            );
+            LegacyOptionContractV0::deserialize(&mut &data[8..])?
+        };
+        require_keys_eq!(
+            ctx.accounts.holder.key(),
+            legacy.holder,
+            QuorumError::UnauthorizedHolder
+        );
+
+        let legacy_key = legacy_info.key();
+        let bump = canonical_option_bump(&option_id, 0, ctx.accounts.option_contract.key(), ctx.program_id)?;
+        let option = &mut ctx.accounts.option_contract;
+        option.set_inner(legacy.into_current(bump));
+
+        // Premium and any strike held move across; the rent reserve goes home
+        let option_info = option.to_account_info();
+        let carried = withdrawable_lamports(&legacy_info)?;
+        transfer_from_pda(&legacy_info, &option_info, carried)?;
+        let holder_info = ctx.accounts.holder.to_account_info();
+        close_program_account(&legacy_info, &holder_info)?;
+
+        let option = &ctx.accounts.option_contract;
+        unindex_option(
+            &ctx.accounts.event_index,
+            &option.event_name,
+            legacy_key,
+            &holder_info,
+            ctx.program_id,
+        )?;
+        index_option(
+            &ctx.accounts.event_index,
+            &option.event_name,
+            option.key(),
+            &holder_info,
+            &ctx.accounts.system_program,
+            ctx.program_id,
+        )?;
+
+        emit!(OptionMigrated {
+            option_id: option_id.clone(),
+            legacy: legacy_key,
+            migrated: option.key(),
+            schema_version: OPTION_SCHEMA_VERSION,
+        });
+
+        msg!("Option migrated: {} — {} → {}, {} lamports carried",
+             option_id, legacy_key, option.key(), carried);
+        Ok(())
+    }
+
+    /// Close a settled (Exercised/Expired/Cancelled) option and reclaim its rent.
+    /// Proceeds held by the PDA — the premium, or whatever a cancel retained —
+    /// go to the writer; the rent-exempt minimum goes back to the creator.
+    /// Active options can't be closed, so live contracts can't be rugged.
+    pub fn close_option(ctx: Context<CloseOption>) -> Result<()> {
+        let option = &ctx.accounts.option_contract;
+
+        // Unclaimed Dutch listings are dead weight once they pass expiry
+        let now = current_timestamp(&ctx.accounts.clock)?;
+        let settled = match option.status_enum()? {
+            OptionStatus::Exercised | OptionStatus::Expired | OptionStatus::Cancelled => true,
+            OptionStatus::Listed => now > option.expiry,
+            // Closed with its compound option, never on its own
+            OptionStatus::Active | OptionStatus::PendingIssuance | OptionStatus::Reserved => false,
+        };
+        require!(settled, QuorumError::NotSettled);
+        require_writer_payout_allowed(option)?;
+        // Closing sweeps the premium too, so it waits out disputes like a withdrawal
+        if option.status_enum()? == OptionStatus::Expired {
+            require_dispute_settled(option, &ctx.accounts.config, now)?;
+        }
+
+        // SPL premiums: sweep the escrow to the writer, then close it
+        if let Some(mint) = option.premium_mint {
+            let (escrow, writer_token, token_program) = unpack_spl_accounts(
+                &ctx.accounts.premium_escrow,
+                &ctx.accounts.writer_token,
+                &ctx.accounts.token_program,
+            )?;
+            require_keys_eq!(writer_token.mint, mint, QuorumError::InvalidRecipient);
+            require_keys_eq!(writer_token.owner, option.writer, QuorumError::InvalidRecipient);
+
+            transfer_from_escrow(option, escrow, writer_token, token_program, escrow.amount)?;
+
+            let nonce = option.client_nonce.to_le_bytes();
+            let seeds: &[&[u8]] = &[b"option", OPTION_SEED_VERSION, option.option_id.as_bytes(), &nonce, &[option.bump]];
+            token::close_account(CpiContext::new_with_signer(
+                token_program.to_account_info(),
+                token::CloseAccount {
+                    account: escrow.to_account_info(),
+                    destination: ctx.accounts.creator.to_account_info(),
+                    authority: option.to_account_info(),
+                },
+                &[seeds],
+            ))?;
+
+            // Nothing is pending, so the strike escrow is empty — just reclaim its rent
+            if option.strike_mint.is_some() {
+                let strike_escrow = ctx
+                    .accounts
+                    .strike_escrow
+                    .as_ref()
+                    .ok_or(QuorumError::MissingTokenAccounts)?;
+                token::close_account(CpiContext::new_with_signer(
+                    token_program.to_account_info(),
+                    token::CloseAccount {
+                        account: strike_escrow.to_account_info(),
+                        destination: ctx.accounts.creator.to_account_info(),
+                        authority: option.to_account_info(),
+                    },
+                    &[seeds],
+                ))?;
+            }
+        }
+
+        // Everything above the rent-exempt minimum belongs to the writer.
+        // The close constraint then returns the rent itself to the creator.
+        let option_info = ctx.accounts.option_contract.to_account_info();
+        let proceeds = withdrawable_lamports(&option_info)?;
+        // An expired premium vests through the WriterVault, so it can't leave by closing
+        require!(
+            proceeds == 0 || option.status_enum()? != OptionStatus::Expired,
+            QuorumError::PremiumNotWithdrawn
+        );
+        if proceeds > 0 {
+            transfer_from_pda(&option_info, &ctx.accounts.writer, proceeds)?;
+        }
+
+        let option = &ctx.accounts.option_contract;
+        unindex_option(
+            &ctx.accounts.event_index,
+            &option.event_name,
+            option.key(),
+            &ctx.accounts.creator,
+            ctx.program_id,
+        )?;
+        emit_lamport_flow(
+            &option.option_id,
+            FlowKind::Withdrawal,
+            proceeds,
+            option.key(),
+            ctx.accounts.writer.key(),
+        );
+        emit!(OptionClosed {
+            option_id: option.option_id.clone(),
+            writer: option.writer,
+            creator: option.creator,
+            proceeds_lamports: proceeds,
+        });
+
+        msg!("Option closed: {} — {} lamports to writer, rent to {}",
+             option.option_id, proceeds, option.creator);
+        Ok(())
+    }
+
+    /// Roll an option forward — holder pushes expiry out, optionally
+    /// topping up the premium to pay for the extra time.
+    pub fn extend_expiry(
+        ctx: Context<ExtendExpiry>,
+        new_expiry: i64,
+        additional_premium: u64,
+    ) -> Result<()> {
+        let option = &ctx.accounts.option_contract;
+
+        require_not_nft_backed(option)?;
+
+        require!(option.status_enum()? == OptionStatus::Active, QuorumError::NotActive);
+        require!(
+            ctx.accounts.holder.key() == option.holder,
+            QuorumError::UnauthorizedHolder
+        );
+
+        let now = current_timestamp(&ctx.accounts.clock)?;
+        require!(now <= option.expiry, QuorumError::OptionExpired);
+        require!(new_expiry > option.expiry, QuorumError::ExpiryNotExtended);
+        require!(new_expiry > now, QuorumError::ExpiryInPast);
+        require_event_upcoming(&ctx.accounts.config, &option.event_date, new_expiry, now)?;
+
+        // Top-ups are SOL only; SPL options can still extend for free
+        require!(
+            additional_premium == 0 || option.premium_mint.is_none(),
+            QuorumError::NativePremiumOnly
+        );
+
+        // Effects before interactions
+        let option = &mut ctx.accounts.option_contract;
+        let old_expiry = option.expiry;
+        option.expiry = new_expiry;
+        // A new expiry deserves its own reminder
+        option.warned = false;
+        option.premium_lamports = option
+            .premium_lamports
+            .checked_add(additional_premium)
+            .ok_or(QuorumError::MathOverflow)?;
+
+        if additional_premium > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.holder.to_account_info(),
+                        to: ctx.accounts.option_contract.to_account_info(),
+                    },
+                ),
+                additional_premium,
+            )?;
+        }
+        emit_lamport_flow(
+            &ctx.accounts.option_contract.option_id,
+            FlowKind::Premium,
+            additional_premium,
+            ctx.accounts.holder.key(),
+            ctx.accounts.option_contract.key(),
+        );
+
+        if additional_premium > 0 {
+            record_event_stats(
+                &ctx.accounts.event_stats,
+                &ctx.accounts.option_contract.event_name,
+                &ctx.accounts.holder,
+                &ctx.accounts.system_program,
+                ctx.program_id,
+                |stats| {
+                    stats.total_premium_collected =
+                        stats.total_premium_collected.saturating_add(additional_premium)
+                },
+            )?;
+        }
+
+        let option = &ctx.accounts.option_contract;
+        emit!(OptionExtended {
+            option_id: option.option_id.clone(),
+            old_expiry,
+            new_expiry,
+            additional_premium,
+        });
+
+        msg!("Option extended: {} — expiry {} → {}, +{} premium",
+             option.option_id, old_expiry, new_expiry, additional_premium);
+        Ok(())
+    }
+
+    /// Holder commits more premium to an Active option without touching its
+    /// expiry — a stronger demand signal, or catching up to a raised minimum.
+    pub fn add_premium(ctx: Context<AddPremium>, amount: u64) -> Result<()> {
+        let option = &ctx.accounts.option_contract;
+
+        require_not_nft_backed(option)?;
+
+        require!(option.status_enum()? == OptionStatus::Active, QuorumError::NotActive);
+        require!(
+            ctx.accounts.holder.key() == option.holder,
+            QuorumError::UnauthorizedHolder
+        );
+        require!(amount > 0, QuorumError::InvalidPremium);
+        require!(option.premium_mint.is_none(), QuorumError::NativePremiumOnly);
+
+        let now = current_timestamp(&ctx.accounts.clock)?;
+        require!(now <= option.expiry, QuorumError::OptionExpired);
+
+        // Effects before interactions
+        let option = &mut ctx.accounts.option_contract;
+        option.premium_lamports = option
+            .premium_lamports
+            .checked_add(amount)
+            .ok_or(QuorumError::MathOverflow)?;
+
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.holder.to_account_info(),
+                    to: ctx.accounts.option_contract.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        let option = &ctx.accounts.option_contract;
+        emit_lamport_flow(&option.option_id, FlowKind::Premium, amount, option.holder, option.key());
+        record_event_stats(
+            &ctx.accounts.event_stats,
+            &option.event_name,
+            &ctx.accounts.holder,
+            &ctx.accounts.system_program,
+            ctx.program_id,
+            |stats| stats.total_premium_collected = stats.total_premium_collected.saturating_add(amount),
+        )?;
+        emit!(PremiumIncreased {
+            option_id: option.option_id.clone(),
+            holder: option.holder,
+            delta: amount,
+            new_total: option.premium_lamports,
+        });
+
+        msg!("Premium increased: {} — +{} to {} lamports",
+             option.option_id, amount, option.premium_lamports);
+        Ok(())
+    }
+
+    /// Writer hands back premium it no longer needs — a pricing correction,
+    /// or less exposure — while the option stays Active. The premium can
+    /// drop to new_premium but never below the config minimum for the
+    /// tickets the option covers; the difference goes back to the holder.
+    pub fn rebalance_premium(ctx: Context<RebalancePremium>, new_premium: u64) -> Result<()> {
+        let option = &ctx.accounts.option_contract;
+
+        require_not_nft_backed(option)?;
+        require!(option.status_enum()? == OptionStatus::Active, QuorumError::NotActive);
+        require!(!option.frozen, QuorumError::OptionFrozen);
+        require!(option.premium_mint.is_none(), QuorumError::NativePremiumOnly);
+
+        require!(new_premium > 0, QuorumError::InvalidPremium);
+        require!(new_premium < option.premium_lamports, QuorumError::InvalidPremium);
+        let tickets = option.quantity.saturating_add(option.exercised_qty);
+        require!(
+            new_premium as u128 >= min_premium(&ctx.accounts.config, option.strike_lamports, tickets),
+            QuorumError::PremiumTooLow
+        );
+        require_premium_bounds(&ctx.accounts.config, new_premium)?;
+
+        let old_premium = option.premium_lamports;
+        let refund = old_premium - new_premium;
+
+        // Effects before interactions
+        ctx.accounts.option_contract.premium_lamports = new_premium;
+
+        let option = &ctx.accounts.option_contract;
+        transfer_from_pda(&option.to_account_info(), &ctx.accounts.holder, refund)?;
+        emit_lamport_flow(&option.option_id, FlowKind::Refund, refund, option.key(), option.holder);
+
+        emit!(PremiumRebalanced {
+            option_id: option.option_id.clone(),
+            writer: option.writer,
+            old_premium,
+            new_premium,
+            refund,
+        });
+
+        msg!("Premium rebalanced: {} — {} → {}, {} back to {}",
+             option.option_id, old_premium, new_premium, refund, option.holder);
+        Ok(())
+    }
+
+    /// Withdraw the retained premium from an expired option.
+    /// Only the writer can sweep — this is the venue's claim on the upside.
+    /// SOL premiums land in the writer's WriterVault and vest over
+    /// config.writer_vesting_seconds; SPL premiums go straight to the writer.
+    pub fn withdraw_expired_premium(ctx: Context<WithdrawExpiredPremium>) -> Result<()> {
+        let option = &ctx.accounts.option_contract;
+
+        require!(option.status_enum()? == OptionStatus::Expired, QuorumError::NotExpired);
+        require_writer_payout_allowed(option)?;
+        require_dispute_settled(option, &ctx.accounts.config, current_timestamp(&ctx.accounts.clock)?)?;
+
+        let amount = match option.premium_mint {
+            Some(mint) => {
+                let (escrow, writer_token, token_program) = unpack_spl_accounts(
+                    &ctx.accounts.premium_escrow,
+                    &ctx.accounts.writer_token,
+                    &ctx.accounts.token_program,
+                )?;
+                require_keys_eq!(writer_token.mint, mint, QuorumError::InvalidRecipient);
+                require_keys_eq!(writer_token.owner, option.writer, QuorumError::InvalidRecipient);
+
+                let amount = escrow.amount;
+                require!(amount > 0, QuorumError::NothingToWithdraw);
+                transfer_from_escrow(option, escrow, writer_token, token_program, amount)?;
+                amount
+            }
+            None => {
+                // Leave the rent-exempt minimum behind; close_option reclaims it
+                let option_info = ctx.accounts.option_contract.to_account_info();
+                let amount = withdrawable_lamports(&option_info)?;
+                require!(amount > 0, QuorumError::NothingToWithdraw);
+
+                let vault = &mut ctx.accounts.writer_vault;
+                if vault.writer == Pubkey::default() {
+                    vault.writer = option.writer;
+                    vault.bump = ctx.bumps.writer_vault;
+                }
+                deposit_writer_vault(vault, amount, &ctx.accounts.config, current_timestamp(&ctx.accounts.clock)?)?;
+
+                transfer_from_pda(&option_info, &vault.to_account_info(), amount)?;
+                emit_lamport_flow(&option.option_id, FlowKind::Withdrawal, amount, option.key(), vault.key());
+                amount
+            }
+        };
+
+        emit!(PremiumWithdrawn {
+            option_id: option.option_id.clone(),
+            writer: option.writer,
+            amount,
+        });
+
+        msg!("Premium withdrawn: {} — {} to writer {}", option.option_id, amount, option.writer);
+        Ok(())
+    }
+
+    /// Writer claims whatever has vested in its WriterVault so far.
+    /// Deposits vest linearly until the vault's vesting_end.
+    pub fn claim_vested(ctx: Context<ClaimVested>) -> Result<()> {
+        let vault = &mut ctx.accounts.writer_vault;
+        vest_writer_vault(vault, current_timestamp(&ctx.accounts.clock)?);
+
+        let vault_info = vault.to_account_info();
+        let amount = vault.unlocked.min(withdrawable_lamports(&vault_info)?);
+        require!(amount > 0, QuorumError::NothingToWithdraw);
+
+        // Effects before interactions
+        vault.unlocked -= amount;
+        vault.total_claimed = vault.total_claimed.saturating_add(amount);
+
+        transfer_from_pda(&vault_info, &ctx.accounts.writer, amount)?;
+        emit_lamport_flow("", FlowKind::Withdrawal, amount, vault.key(), vault.writer);
+
+        emit!(VestedClaimed {
+            writer: vault.writer,
+            amount,
+            still_locked: vault.locked,
+            total_claimed: vault.total_claimed,
+        });
+
+        msg!("Vested premium claimed: {} to writer {}, {} still locked",
+             amount, vault.writer, vault.locked);
+        Ok(())
+    }
+
+    /// Holder flags an expired option they couldn't exercise (e.g. a venue
+    /// outage). Must come within config.dispute_window_seconds of the
+    /// exercise deadline; the premium stays frozen until the authority
+    /// resolves it.
+    pub fn raise_dispute(ctx: Context<RaiseDispute>, reason: String) -> Result<()> {
+        require!(reason.len() <= 200, QuorumError::StringTooLong);
+
+        let option = &mut ctx.accounts.option_contract;
+        require!(option.status_enum()? == OptionStatus::Expired, QuorumError::NotExpired);
+        require!(
+            ctx.accounts.holder.key() == option.holder,
+            QuorumError::UnauthorizedHolder
+        );
+        require_not_nft_backed(option)?;
+        require!(!option.disputed, QuorumError::DisputeOpen);
+
+        let now = current_timestamp(&ctx.accounts.clock)?;
+        require!(
+            now <= dispute_deadline(option, &ctx.accounts.config),
+            QuorumError::DisputeWindowClosed
+        );
+
+        option.disputed = true;
+
+        emit!(DisputeRaised {
+            option_id: option.option_id.clone(),
+            holder: option.holder,
+            reason,
+            timestamp: now,
+        });
+
+        msg!("Dispute raised: {} by {}", option.option_id, option.holder);
+        Ok(())
+    }
+
+    /// Authority settles a dispute. refund_holder = true returns the
+    /// retained premium to the holder; false releases it to the writer,
+    /// who can then withdraw as usual.
+    pub fn resolve_dispute(ctx: Context<ResolveDispute>, refund_holder: bool) -> Result<()> {
+        let option = &ctx.accounts.option_contract;
+        require!(option.disputed, QuorumError::NotDisputed);
+
+        // Effects before interactions
+        ctx.accounts.option_contract.disputed = false;
+
+        let option = &ctx.accounts.option_contract;
+        let refund = if !refund_holder {
+            0
+        } else {
+            match option.premium_mint {
+                Some(mint) => {
+                    let (escrow, holder_token, token_program) = unpack_spl_accounts(
+                        &ctx.accounts.premium_escrow,
+                        &ctx.accounts.holder_token,
+                        &ctx.accounts.token_program,
+                    )?;
+                    require_keys_eq!(holder_token.mint, mint, QuorumError::InvalidRecipient);
+                    require_keys_eq!(holder_token.owner, option.holder, QuorumError::InvalidRecipient);
+                    let refund = escrow.amount;
+                    transfer_from_escrow(option, escrow, holder_token, token_program, refund)?;
+                    refund
+                }
+                None => {
+                    // Never dip into the rent reserve — close_option still needs it
+                    let option_info = option.to_account_info();
+                    // Strike awaiting issuance isn't premium; it settles on its own
+                    let refund = withdrawable_lamports(&option_info)?.saturating_sub(option.pending_strike);
+                    transfer_from_pda(&option_info, &ctx.accounts.holder.to_account_info(), refund)?;
+                    emit_lamport_flow(&option.option_id, FlowKind::Refund, refund, option.key(), option.holder);
+                    refund
+                }
+            }
+        };
+        let option = &mut ctx.accounts.option_contract;
+        option.premium_lamports = option.premium_lamports.saturating_sub(refund);
+
+        emit!(DisputeResolved {
+            option_id: option.option_id.clone(),
+            holder: option.holder,
+            refunded: refund_holder,
+            refund,
+        });
+
+        msg!("Dispute resolved: {} — refund to holder: {}", option.option_id, refund);
+        Ok(())
+    }
+
+    /// Cancel an active option — fan walks away before expiry.
+    /// Refunds up to cancel_refund_bps of the premium to the holder, decaying
+    /// to nothing at expiry (see cancel_refund); the rest stays in the PDA as
+    /// a cancellation fee for the writer.
+    pub fn cancel_option(ctx: Context<CancelOption>) -> Result<()> {
+        let option = &ctx.accounts.option_contract;
+
+        require_not_nft_backed(option)?;
+        require!(option.co_holders.is_empty(), QuorumError::CoHeld);
+
+        require!(option.status_enum()? == OptionStatus::Active, QuorumError::NotActive);
+        require!(!option.frozen, QuorumError::OptionFrozen);
+        require!(
+            ctx.accounts.holder.key() == option.holder,
+            QuorumError::UnauthorizedHolder
+        );
+
+        let now = current_timestamp(&ctx.accounts.clock)?;
+        require!(now <= option.expiry, QuorumError::OptionExpired);
+
+        let refund = cancel_refund(option, now);
+        let fee_retained = option.premium_lamports.checked_sub(refund).ok_or(QuorumError::MathOverflow)?;
+
+        // Effects before interactions: the option is dead before the refund moves
+        ctx.accounts.option_contract.set_status(OptionStatus::Cancelled)?;
+        remove_active_option(&mut ctx.accounts.holder_stats);
+        ctx.accounts.global_stats.record_cancelled(true);
+        ctx.accounts.holder_stats.last_action_ts = now;
+
+        let option = &ctx.accounts.option_contract;
+        // The cancelled option's slot under the event cap opens up again
+        release_event_slot(&ctx.accounts.event_registry, option.writer, &option.event_name, ctx.program_id)?;
+        if refund > 0 {
+            match option.premium_mint {
+                Some(mint) => {
+                    let (escrow, holder_token, token_program) = unpack_spl_accounts(
+                        &ctx.accounts.premium_escrow,
+                        &ctx.accounts.holder_token,
+                        &ctx.accounts.token_program,
+                    )?;
+                    require_keys_eq!(holder_token.mint, mint, QuorumError::InvalidRecipient);
+                    require_keys_eq!(holder_token.owner, option.holder, QuorumError::InvalidRecipient);
+                    transfer_from_escrow(option, escrow, holder_token, token_program, refund)?;
+                }
+                None => {
+                    transfer_from_pda(
+                        &option.to_account_info(),
+                        &ctx.accounts.holder.to_account_info(),
+                        refund,
+                    )?;
+                    emit_lamport_flow(&option.option_id, FlowKind::Refund, refund, option.key(), option.holder);
+                }
+            }
+        }
+        refund_strike_escrow(&mut ctx.accounts.option_contract, &ctx.accounts.holder)?;
+
+        let option = &ctx.accounts.option_contract;
+        emit!(OptionCancelled {
+            option_id: option.option_id.clone(),
+            holder: option.holder,
+            refund,
+            fee_retained,
+        });
+        emit_status_changed(option, OptionStatus::Active as u8, now);
+
+        msg!("Option cancelled: {} — refunded {}, retained {}",
+             option.option_id, refund, fee_retained);
+        Ok(())
+    }
+
+    /// Writer flags (or un-flags) one of its events as cancelled by the venue.
+    /// event_hash is event_seed(event_name).
+    pub fn set_event_cancelled(
+        ctx: Context<UpdateEventRegistry>,
+        event_hash: [u8; 32],
+        cancelled: bool,
+    ) -> Result<()> {
+        let registry = &mut ctx.accounts.registry;
+        init_event_registry(registry, ctx.accounts.writer.key(), event_hash, ctx.bumps.registry);
+        registry.cancelled = cancelled;
+
+        emit!(EventCancelled {
+            writer: registry.writer,
+            event_hash,
+            cancelled,
+        });
+
+        msg!("Event {} by writer {}", if cancelled { "cancelled" } else { "reinstated" }, registry.writer);
+        Ok(())
+    }
+
+    /// Writer pins the canonical ticket_type labels for one of its events.
+    /// Once set, every new option for the event must use one of them; an
+    /// empty list lifts the restriction.
+    pub fn set_ticket_types(
+        ctx: Context<UpdateEventRegistry>,
+        event_hash: [u8; 32],
+        ticket_types: Vec<String>,
+    ) -> Result<()> {
+        require!(ticket_types.len() <= MAX_TICKET_TYPES, QuorumError::TooManyTicketTypes);
+        for ticket_type in &ticket_types {
+            require!(!ticket_type.is_empty(), QuorumError::EmptyString);
+            require!(ticket_type.len() <= MAX_TICKET_TYPE_LEN as usize, QuorumError::StringTooLong);
+        }
+
+        let registry = &mut ctx.accounts.registry;
+        init_event_registry(registry, ctx.accounts.writer.key(), event_hash, ctx.bumps.registry);
+        registry.ticket_types = ticket_types;
+
+        emit!(TicketTypesSet {
+            writer: registry.writer,
+            event_hash,
+            ticket_types: registry.ticket_types.clone(),
+        });
+
+        msg!("Ticket types set: {} for writer {}", registry.ticket_types.len(), registry.writer);
+        Ok(())
+    }
+
+    /// Writer sets (or clears, with None) the presale allowlist for one of
+    /// its events: a merkle root over sha256(fan pubkey) leaves. While set,
+    /// Listed options for the event can only be claimed with a proof.
+    pub fn set_presale_root(
+        ctx: Context<UpdateEventRegistry>,
+        event_hash: [u8; 32],
+        presale_root: Option<[u8; 32]>,
+    ) -> Result<()> {
+        let registry = &mut ctx.accounts.registry;
+        init_event_registry(registry, ctx.accounts.writer.key(), event_hash, ctx.bumps.registry);
+        registry.presale_root = presale_root;
+
+        emit!(PresaleRootSet {
+            writer: registry.writer,
+            event_hash,
+            presale_root,
+        });
+
+        msg!("Presale allowlist {} for writer {}",
+             if presale_root.is_some() { "set" } else { "cleared" }, registry.writer);
+        Ok(())
+    }
+
+    /// Writer pins (or clears, with 0) one expiry for every option on one of
+    /// its events — typically doors-close. New options take it in place of
+    /// their own expiry; options already written keep theirs.
+    pub fn set_event_expiry(
+        ctx: Context<UpdateEventRegistry>,
+        event_hash: [u8; 32],
+        event_expiry: i64,
+    ) -> Result<()> {
+        let now = current_timestamp(&ctx.accounts.clock)?;
+        require!(
+            event_expiry == 0 || event_expiry > now,
+            QuorumError::ExpiryInPast
+        );
+
+        let registry = &mut ctx.accounts.registry;
+        init_event_registry(registry, ctx.accounts.writer.key(), event_hash, ctx.bumps.registry);
+        registry.event_expiry = event_expiry;
+
+        emit!(EventExpirySet {
+            writer: registry.writer,
+            event_hash,
+            event_expiry,
+        });
+
+        msg!("Event expiry for writer {} set to {}", registry.writer, event_expiry);
+        Ok(())
+    }
+
+    /// Writer caps how many options can be written for one of its events
+    /// (0 lifts the cap), so issuance can't outrun real inventory. Options
+    /// count from the moment the registry exists; cancelling one frees its
+    /// slot. Lowering the cap below what's issued only blocks new ones.
+    pub fn set_event_max_options(
+        ctx: Context<UpdateEventRegistry>,
+        event_hash: [u8; 32],
+        max_options: u32,
+    ) -> Result<()> {
+        let registry = &mut ctx.accounts.registry;
+        init_event_registry(registry, ctx.accounts.writer.key(), event_hash, ctx.bumps.registry);
+        registry.max_options = max_options;
+
+        emit!(EventMaxOptionsSet {
+            writer: registry.writer,
+            event_hash,
+            max_options,
+            options_issued: registry.options_issued,
+        });
+
+        msg!("Event option cap for writer {} set to {} ({} issued)",
+             registry.writer, max_options, registry.options_issued);
+        Ok(())
+    }
+
+    /// Writer makes one of its events sell in lots — pairs, tables — so new
+    /// options must cover whole lots and partial exercises take whole lots
+    /// (0 or 1 lifts it). Options already written keep their quantity and
+    /// can always exercise everything they have left.
+    pub fn set_event_lot_size(
+        ctx: Context<UpdateEventRegistry>,
+        event_hash: [u8; 32],
+        lot_size: u8,
+    ) -> Result<()> {
+        let registry = &mut ctx.accounts.registry;
+        init_event_registry(registry, ctx.accounts.writer.key(), event_hash, ctx.bumps.registry);
+        registry.lot_size = lot_size;
+
+        emit!(EventLotSizeSet {
+            writer: registry.writer,
+            event_hash,
+            lot_size,
+        });
+
+        msg!("Event lot size for writer {} set to {}", registry.writer, lot_size);
+        Ok(())
+    }
+
+    /// Writer names the account its event's strike payouts settle to, so
+    /// confirm_issuance can only pay the writer's share there (default
+    /// Pubkey: the option's writer). Pending exercises settle to whatever is
+    /// set when the venue confirms them.
+    pub fn set_event_settlement_account(
+        ctx: Context<UpdateEventRegistry>,
+        event_hash: [u8; 32],
+        settlement_account: Pubkey,
+    ) -> Result<()> {
+        let registry = &mut ctx.accounts.registry;
+        init_event_registry(registry, ctx.accounts.writer.key(), event_hash, ctx.bumps.registry);
+        registry.settlement_account = settlement_account;
+
+        emit!(EventSettlementAccountSet {
+            writer: registry.writer,
+            event_hash,
+            settlement_account,
+        });
+
+        msg!("Event settlement account for writer {} set to {}", registry.writer, settlement_account);
+        Ok(())
+    }
+
+    /// Writer sets quantity-based royalty tiers for one of its events.
+    /// At exercise, the tier with the highest min_quantity the option's
+    /// ticket count reaches wins; below every tier (or with none set) the
+    /// option's own venue_royalty_bps applies. Each tier is capped at
+    /// config.max_royalty_bps.
+    pub fn set_royalty_tiers(
+        ctx: Context<UpdateEventRegistry>,
+        event_hash: [u8; 32],
+        royalty_tiers: Vec<RoyaltyTier>,
+    ) -> Result<()> {
+        require!(royalty_tiers.len() <= MAX_ROYALTY_TIERS, QuorumError::TooManyRoyaltyTiers);
+        for tier in &royalty_tiers {
+            require!(tier.min_quantity > 0, QuorumError::InvalidQuantity);
+            require!(tier.bps <= ctx.accounts.config.max_royalty_bps, QuorumError::InvalidRoyalty);
+        }
+
+        let registry = &mut ctx.accounts.registry;
+        init_event_registry(registry, ctx.accounts.writer.key(), event_hash, ctx.bumps.registry);
+        registry.royalty_tiers = royalty_tiers;
+
+        emit!(RoyaltyTiersSet {
+            writer: registry.writer,
+            event_hash,
+            royalty_tiers: registry.royalty_tiers.clone(),
+        });
+
+        msg!("Royalty tiers set: {} for writer {}", registry.royalty_tiers.len(), registry.writer);
+        Ok(())
+    }
+
+    /// Reveal-then-price close: the writer fixes one clearing premium for an
+    /// event and refunds everyone who paid more the difference. Works in
+    /// batches — remaining_accounts are (option, holder wallet) pairs — and
+    /// the price can't change once set, so every holder clears at the same
+    /// level. Already-refunded, non-Active and SPL options are skipped.
+    /// Premiums are compared net of protocol fee, i.e. what each PDA holds.
+    pub fn finalize_clearing_price<'info>(
+        ctx: Context<'_, '_, 'info, 'info, FinalizeClearingPrice<'info>>,
+        clearing_premium: u64,
+    ) -> Result<()> {
+        require!(clearing_premium > 0, QuorumError::InvalidPremium);
+        let entries = ctx.remaining_accounts.len() / 2;
+        require!(
+            ctx.remaining_accounts.len() % 2 == 0,
+            QuorumError::BatchAccountsMismatch
+        );
+        require!(entries > 0 && entries <= MAX_BATCH_SIZE, QuorumError::InvalidBatchSize);
+
+        let registry = &mut ctx.accounts.registry;
+        if registry.clearing_premium == 0 {
+            registry.clearing_premium = clearing_premium;
+        }
+        require!(
+            registry.clearing_premium == clearing_premium,
+            QuorumError::ClearingPriceSet
+        );
+
+        let writer = ctx.accounts.writer.key();
+        let event_hash = registry.event_hash;
+        let mut refunded: u32 = 0;
+
+        for entry in ctx.remaining_accounts.chunks(2) {
+            let mut option: Account<'info, OptionContract> = Account::try_from(&entry[0])?;
+            require_keys_eq!(option.writer, writer, QuorumError::UnauthorizedWriter);
+            require!(event_seed(&option.event_name) == event_hash, QuorumError::WrongEvent);
+            if option.status_enum()? != OptionStatus::Active
+                || option.premium_mint.is_some()
+                || option.premium_lamports <= clearing_premium
+            {
+                continue;
+            }
+            require_keys_eq!(entry[1].key(), option.holder, QuorumError::InvalidRecipient);
+
+            // Effects before interactions
+            let refund = option
+                .premium_lamports
+                .checked_sub(clearing_premium)
+                .ok_or(QuorumError::MathOverflow)?;
+            option.premium_lamports = clearing_premium;
+            option.exit(ctx.program_id)?;
+
+            transfer_from_pda(&entry[0], &entry[1], refund)?;
+            emit_lamport_flow(&option.option_id, FlowKind::Refund, refund, entry[0].key(), option.holder);
+
+            emit!(ClearingRefund {
+                option_id: option.option_id.clone(),
+                holder: option.holder,
+                refund,
+                clearing_premium,
+            });
+            refunded += 1;
+        }
+
+        msg!("Clearing premium {} — refunded {} of {} options", clearing_premium, refunded, entries);
+        Ok(())
+    }
+
+    /// Show got cancelled: the holder gets the full premium back, expiry or
+    /// not. Also covers options a keeper already expired, up to whatever
+    /// premium the option still holds.
+    pub fn claim_event_cancellation_refund(ctx: Context<ClaimCancellationRefund>) -> Result<()> {
+        let option = &ctx.accounts.option_contract;
+        let registry = &ctx.accounts.registry;
+
+        let from_status = option.status_enum()?;
+        let was_active = from_status == OptionStatus::Active;
+        require!(
+            was_active || from_status == OptionStatus::Expired,
+            QuorumError::NotActive
+        );
+        require!(
+            ctx.accounts.holder.key() == option.holder,
+            QuorumError::UnauthorizedHolder
+        );
+        require_not_nft_backed(option)?;
+        require_keys_eq!(registry.writer, option.writer, QuorumError::WrongEvent);
+        require!(
+            registry.event_hash == event_seed(&option.event_name),
+            QuorumError::WrongEvent
+        );
+        require!(registry.cancelled, QuorumError::EventNotCancelled);
+
+        // Effects before interactions: the option is dead before the refund moves
+        let option = &mut ctx.accounts.option_contract;
+        option.set_status(OptionStatus::Cancelled)?;
+        if was_active {
+            remove_active_option(&mut ctx.accounts.holder_stats);
+        }
+        ctx.accounts.global_stats.record_cancelled(was_active);
+        refund_strike_escrow(&mut ctx.accounts.option_contract, &ctx.accounts.holder)?;
+
+        let option = &ctx.accounts.option_contract;
+        let refund = match option.premium_mint {
+            Some(mint) => {
+                let (escrow, holder_token, token_program) = unpack_spl_accounts(
+                    &ctx.accounts.premium_escrow,
+                    &ctx.accounts.holder_token,
+                    &ctx.accounts.token_program,
+                )?;
+                require_keys_eq!(holder_token.mint, mint, QuorumError::InvalidRecipient);
+                require_keys_eq!(holder_token.owner, option.holder, QuorumError::InvalidRecipient);
+                let refund = option.premium_lamports.min(escrow.amount);
+                transfer_from_escrow(option, escrow, holder_token, token_program, refund)?;
+                refund
+            }
+            None => {
+                // Never dip into the rent reserve — close_option still needs it
+                let option_info = option.to_account_info();
+                let available = withdrawable_lamports(&option_info)?.saturating_sub(option.pending_strike);
+                let refund = option.premium_lamports.min(available);
+                transfer_from_pda(&option_info, &ctx.accounts.holder.to_account_info(), refund)?;
+                emit_lamport_flow(&option.option_id, FlowKind::Refund, refund, option.key(), option.holder);
+                refund
+            }
+        };
+        let option = &mut ctx.accounts.option_contract;
+        option.premium_lamports = option
+            .premium_lamports
+            .checked_sub(refund)
+            .ok_or(QuorumError::MathOverflow)?;
+
+        let option = &ctx.accounts.option_contract;
+        emit!(CancellationRefund {
+            option_id: option.option_id.clone(),
+            holder: option.holder,
+            refund,
+        });
+        emit_status_changed(option, from_status as u8, current_timestamp(&ctx.accounts.clock)?);
+
+        msg!("Event cancelled — refunded {} on {} to {}", refund, option.option_id, option.holder);
+        Ok(())
+    }
+}
+
+// ============================================================================
+// HELPERS
+// ============================================================================
+
+/// Cap on options per create_options_batch call — keeps us well inside
+/// transaction size and compute limits.
+pub const MAX_BATCH_SIZE: usize = 10;
+
+/// 1 micro-SOL = 1_000 lamports — the unit of OptionCreated.premium_sol_display.
+pub const LAMPORTS_PER_MICRO_SOL: u64 = 1_000;
+
+/// Layout version stamped on every OptionContract. Bump it with any change
+/// to the account layout, alongside a mapping in migrate_option.
+pub const OPTION_SCHEMA_VERSION: u8 = 1;
+
+/// Version byte in the option PDA seeds, so accounts of different layouts
+/// live at different addresses. Version 0 accounts seeded without it.
+pub const OPTION_SEED_VERSION: &[u8] = &[OPTION_SCHEMA_VERSION];
+
+/// Bytes reserved in OptionContract for each string. ProgramConfig's
+/// max_*_len caps start here and can only be tightened: account space is
+/// fixed at creation, so a cap above these would overflow the account.
+/// option_id is also a PDA seed, which Solana limits to 32 bytes.
+pub const MAX_OPTION_ID_LEN: u8 = 32;
+pub const MAX_EVENT_NAME_LEN: u8 = 64;
+pub const MAX_EVENT_DATE_LEN: u8 = 16;
+pub const MAX_TICKET_TYPE_LEN: u8 = 32;
+
+/// Bytes reserved for OptionContract::metadata_uri, the off-chain JSON
+/// (artwork, seat map) wallets and marketplaces render. Not config-capped.
+pub const MAX_METADATA_URI_LEN: u8 = 128;
+
+/// Fixed-point unit of EventStats::demand_score: a premium equal to the
+/// option's full face value scores DEMAND_SCORE_SCALE.
+pub const DEMAND_SCORE_SCALE: u64 = 1_000_000;
+
+/// Weight of the demand_score moving average — each new option moves the
+/// score 1/DEMAND_SCORE_WINDOW of the way toward its own ratio.
+pub const DEMAND_SCORE_WINDOW: u64 = 16;
+
+/// Samples EventStats::price_history keeps before overwriting the oldest.
+pub const PRICE_HISTORY_LEN: usize = 16;
+
+/// How far past the start (00:00 UTC) of event_date a fresh config lets
+/// expiry run — the whole event day, since evening shows start late.
+pub const DEFAULT_EVENT_EXPIRY_BUFFER_SECONDS: u32 = 24 * 60 * 60;
+
+/// Tickets-per-option cap a fresh config starts with.
+pub const DEFAULT_MAX_QUANTITY: u8 = 20;
+
+/// How long a fresh config gives venues to confirm ticket issuance.
+pub const DEFAULT_ISSUANCE_TIMEOUT_SECONDS: u32 = 7 * 24 * 60 * 60;
+
+/// How far ahead of expiry a fresh config lets bots warn holders.
+pub const DEFAULT_EXPIRY_WARNING_SECONDS: u32 = 24 * 60 * 60;
+
+/// Royalty ceiling a fresh config starts with (50%).
+pub const DEFAULT_MAX_ROYALTY_BPS: u16 = 5000;
+
+/// Max canonical ticket types an EventRegistry can hold.
+pub const MAX_TICKET_TYPES: usize = 8;
+
+/// Max options one EventIndex tracks — keeps its deserialized Vec well
+/// inside the program's 32KB heap. Options past it go unindexed; clients
+/// fall back to a getProgramAccounts scan.
+pub const MAX_INDEXED_OPTIONS: usize = 256;
+
+/// Max wallets that can co-hold one option.
+pub const MAX_CO_HOLDERS: usize = 5;
+
+/// Longest presale merkle proof accepted — 2^20 allowlisted fans.
+pub const MAX_PROOF_LEN: usize = 20;
+
+/// Max royalty tiers an EventRegistry can hold.
+pub const MAX_ROYALTY_TIERS: usize = 4;
+
+/// Max resting bids per MarketBook — keeps the account a fixed size.
+pub const MAX_BOOK_BIDS: usize = 16;
+
+/// Canonical form of an event name for keying: trimmed, lowercased, runs
+/// of whitespace collapsed to one space. "  Florist\t LIVE " → "florist live".
+/// Options keep the name as entered; only the per-event PDAs use this.
+pub fn normalize_event_name(event_name: &str) -> String {
+    event_name.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase()
+}
+
+/// Fixed-size seed for per-event PDAs — event names can run past the
+/// 32-byte seed limit, so we key on the sha256 of the normalized name.
+pub fn event_seed(event_name: &str) -> [u8; 32] {
+    anchor_lang::solana_program::hash::hash(normalize_event_name(event_name).as_bytes()).to_bytes()
+}
+
+/// The option lifecycle. Creation sets the initial Active/Listed status
+/// directly; every move after that goes through OptionContract::set_status.
+/// A Reserved placeholder never transitions: exercise_compound_option
+/// writes a fresh option over it, and expire_compound_option closes it.
+///   Listed          → Active (claimed)
+///   Active          → PendingIssuance (fully exercised), Expired, Cancelled
+///   PendingIssuance → Exercised (confirmed), Active (issuance refunded)
+///   Expired         → Cancelled (event cancelled before the sweep)
+/// Exercised and Cancelled are terminal.
+pub fn can_transition(from: OptionStatus, to: OptionStatus) -> bool {
+    use OptionStatus::*;
+    matches!(
+        (from, to),
+        (Listed, Active)
+            | (Active, PendingIssuance)
+            | (Active, Expired)
+            | (Active, Cancelled)
+            | (PendingIssuance, Exercised)
+            | (PendingIssuance, Active)
+            | (Expired, Cancelled)
+    )
+}
+
+/// Validation shared by every option creation path (SOL, SPL and batch).
+fn validate_option_terms(params: &CreateOptionParams, config: &ProgramConfig, now: i64) -> Result<()> {
+    require!(
+        params.quantity > 0 && params.quantity <= config.max_quantity,
+        QuorumError::InvalidQuantity
+    );
+    require!(params.premium_lamports > 0, QuorumError::InvalidPremium);
+    require!(params.strike_lamports > 0, QuorumError::InvalidStrike);
+    // option_id is a PDA seed — an empty one makes a degenerate account
+    require!(!params.option_id.is_empty(), QuorumError::EmptyString);
+    require!(
+        params.option_id.len() <= config.max_option_id_len as usize,
+        QuorumError::StringTooLong
+    );
+    validate_metadata(config, &params.event_name, &params.event_date, &params.ticket_type)?;
+    // One identity can't stand for a whole group of co-holders
+    require!(
+        params.identity_hash.is_none() || params.co_holders.is_empty(),
+        QuorumError::IdentityUnsupported
+    );
+    if let Some(metadata_uri) = &params.metadata_uri {
+        require!(metadata_uri.len() <= MAX_METADATA_URI_LEN as usize, QuorumError::StringTooLong);
+    }
+    require!(params.venue_royalty_bps <= config.max_royalty_bps, QuorumError::InvalidRoyalty);
+    require!(params.cancel_refund_bps <= 10_000, QuorumError::InvalidRefundBps);
+    require!(params.expiry > now, QuorumError::ExpiryInPast);
+    require!(
+        params.expiry >= now + config.min_expiry_seconds as i64,
+        QuorumError::ExpiryTooSoon
+    );
+    require_event_upcoming(config, &params.event_date, params.expiry, now)?;
+    require!(
+        params.exercise_window_start <= params.expiry,
+        QuorumError::InvalidExerciseWindow
+    );
+    validate_co_holders(&params.co_holders, params.quantity)
+}
+
+/// Co-holder shares must cover the option's tickets exactly, one entry per wallet.
+fn validate_co_holders(co_holders: &[CoHolder], quantity: u8) -> Result<()> {
+    if co_holders.is_empty() {
+        return Ok(());
+    }
+    require!(co_holders.len() <= MAX_CO_HOLDERS, QuorumError::InvalidCoHolders);
+    let mut total: u16 = 0;
+    for (i, co_holder) in co_holders.iter().enumerate() {
+        require!(co_holder.shares > 0, QuorumError::InvalidCoHolders);
+        require!(
+            co_holders[..i].iter().all(|other| other.holder != co_holder.holder),
+            QuorumError::InvalidCoHolders
+        );
+        total += co_holder.shares as u16;
+    }
+    require!(total == quantity as u16, QuorumError::InvalidCoHolders);
+    Ok(())
+}
+
+/// SOL premiums must be at least config.min_premium_bps of the total face
+/// value (strike × quantity). SPL premiums are in token units and can't be
+/// compared against a lamport strike, so that path skips this.
+fn require_min_premium(config: &ProgramConfig, premium: u64, params: &CreateOptionParams) -> Result<()> {
+    require!(
+        premium as u128 >= min_premium(config, params.strike_lamports, params.quantity),
+        QuorumError::PremiumTooLow
+    );
+    require_premium_bounds(config, premium)
+}
+
+/// Absolute floor and ceiling on a SOL premium, independent of face value.
+/// Zero on either side means that side is unbounded.
+fn require_premium_bounds(config: &ProgramConfig, premium: u64) -> Result<()> {
+    require!(premium >= config.min_premium_lamports, QuorumError::PremiumTooLow);
+    require!(
+        config.max_premium_lamports == 0 || premium <= config.max_premium_lamports,
+        QuorumError::PremiumTooHigh
+    );
+    Ok(())
+}
+
+/// config.min_premium_bps of the face value of `quantity` tickets at `strike`.
+fn min_premium(config: &ProgramConfig, strike: u64, quantity: u8) -> u128 {
+    let face_value = strike as u128 * quantity as u128;
+    face_value * config.min_premium_bps as u128 / 10_000
+}
+
+/// Cheap YYYY-MM-DD check on the raw bytes — no allocation. The day is
+/// checked against its month's length, Feb 29 only in leap years.
+fn is_iso_date(date: &str) -> bool {
+    let b = date.as_bytes();
+    if b.len() != 10 || b[4] != b'-' || b[7] != b'-' {
+        return false;
+    }
+    let digits = [0, 1, 2, 3, 5, 6, 8, 9];
+    if !digits.iter().all(|&i| b[i].is_ascii_digit()) {
+        return false;
+    }
+    let year = b[..4].iter().fold(0u32, |acc, &d| acc * 10 + (d - b'0') as u32);
+    let month = (b[5] - b'0') * 10 + (b[6] - b'0');
+    let day = (b[8] - b'0') * 10 + (b[9] - b'0');
+    let leap = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+    let month_days = match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if leap => 29,
+        2 => 28,
+        _ => return false,
+    };
+    (1..=month_days).contains(&day)
+}
+
+/// Unix timestamp of 00:00 UTC on a date that passed is_iso_date.
+/// Days-from-civil over the proleptic Gregorian calendar.
+fn iso_date_timestamp(date: &str) -> i64 {
+    let b = date.as_bytes();
+    let num = |range: std::ops::Range<usize>| {
+        b[range].iter().fold(0i64, |acc, &d| acc * 10 + (d - b'0') as i64)
+    };
+    let (year, month, day) = (num(0..4), num(5..7), num(8..10));
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    (era * 146_097 + day_of_era - 719_468) * 86_400
+}
+
+/// The show must still be ahead: its day (UTC) can't be over. And the
+/// option can't outlive it — exercising after the show is meaningless —
+/// so expiry may run at most config.event_expiry_buffer_seconds past the
+/// start of event_date.
+fn require_event_upcoming(config: &ProgramConfig, event_date: &str, expiry: i64, now: i64) -> Result<()> {
+    let event_start = iso_date_timestamp(event_date);
+    require!(now < event_start + 86_400, QuorumError::EventAlreadyPassed);
+    require!(
+        expiry <= event_start + config.event_expiry_buffer_seconds as i64,
+        QuorumError::ExpiryAfterEvent
+    );
+    Ok(())
+}
+
+/// Event metadata rules, shared by creation and update_metadata.
+/// Lengths are checked against the config caps, never the reserved space.
+fn validate_metadata(
+    config: &ProgramConfig,
+    event_name: &str,
+    event_date: &str,
+    ticket_type: &str,
+) -> Result<()> {
+    require!(!event_name.is_empty(), QuorumError::EmptyString);
+    require!(event_name.len() <= config.max_event_name_len as usize, QuorumError::StringTooLong);
+    require!(event_date.len() <= config.max_event_date_len as usize, QuorumError::StringTooLong);
+    require!(is_iso_date(event_date), QuorumError::InvalidEventDate);
+    require!(ticket_type.len() <= config.max_ticket_type_len as usize, QuorumError::StringTooLong);
+    Ok(())
+}
+
+/// Permissioned deployments: with the whitelist on, the writer must have a
+/// WriterWhitelist entry and pass it in.
+fn require_whitelisted(
+    config: &ProgramConfig,
+    entry: &Option<Account<WriterWhitelist>>,
+    writer: Pubkey,
+) -> Result<()> {
+    if !config.whitelist_enabled {
+        return Ok(());
+    }
+    match entry {
+        Some(entry) if entry.writer == writer => Ok(()),
+        _ => err!(QuorumError::WriterNotWhitelisted),
+    }
+}
+
+/// Create paths with the whitelist on: the signer must be whitelisted and
+/// write the option itself — naming some other whitelisted writer, who
+/// never signs, doesn't count.
+fn require_whitelisted_signer(
+    config: &ProgramConfig,
+    entry: &Option<Account<WriterWhitelist>>,
+    signer: Pubkey,
+    writer: Pubkey,
+) -> Result<()> {
+    if config.whitelist_enabled {
+        require_keys_eq!(writer, signer, QuorumError::WriterNotWhitelisted);
+    }
+    require_whitelisted(config, entry, signer)
+}
+
+/// The checks every create path runs before an option exists, so the paths
+/// can't drift apart: pause, the event's cancellation and pinned expiry, the
+/// terms, the whitelist, the premium, and the writer's ticket types and lot size.
+/// `lowest_premium` is the least the option can sell for (a Dutch floor, or
+/// the premium itself); token premiums pass None and skip the lamport
+/// bounds. The writer defaults to the signer and is pinned on params, which
+/// is what this returns.
+#[allow(clippy::too_many_arguments)]
+fn validate_create(
+    config: &ProgramConfig,
+    writer_entry: &Option<Account<WriterWhitelist>>,
+    registry_info: &AccountInfo,
+    params: &mut CreateOptionParams,
+    signer: Pubkey,
+    lowest_premium: Option<u64>,
+    now: i64,
+    program_id: &Pubkey,
+) -> Result<Pubkey> {
+    require!(!config.paused, QuorumError::ProgramPaused);
+    let writer = params.writer.unwrap_or(signer);
+    params.writer = Some(writer);
+    let registry = load_event_registry(registry_info, writer, &params.event_name, program_id)?;
+    require!(
+        !registry.is_some_and(|registry| registry.cancelled),
+        QuorumError::EventWasCancelled
+    );
+    apply_event_expiry(registry_info, writer, params, program_id)?;
+    validate_option_terms(params, config, now)?;
+    require_whitelisted_signer(config, writer_entry, signer, writer)?;
+    if let Some(lowest) = lowest_premium {
+        require_min_premium(config, lowest, params)?;
+        require_premium_bounds(config, params.premium_lamports)?;
+    }
+    require_known_ticket_type(registry_info, writer, &params.event_name, &params.ticket_type, program_id)?;
+    require_lot_terms(registry_info, writer, params, program_id)?;
+    Ok(writer)
+}
+
+/// Royalty rate for an exercise: the event's tier for the option's ticket
+/// count if the writer set tiers, else the option's venue_royalty_bps.
+fn effective_royalty_bps(registry: Option<&EventRegistry>, option: &OptionContract) -> u16 {
+    let Some(registry) = registry else {
+        return option.venue_royalty_bps;
+    };
+    // Tiers key off everything the option covers, not just what's left
+    let tickets = option.quantity.saturating_add(option.exercised_qty);
+    let tier = registry
+        .royalty_tiers
+        .iter()
+        .filter(|tier| tickets >= tier.min_quantity)
+        .max_by_key(|tier| tier.min_quantity);
+    tier.map_or(option.venue_royalty_bps, |tier| tier.bps)
+}
+
+/// Where an exercised option's strike remainder lands: the event's
+/// settlement account when the writer has set one, else the writer.
+fn settlement_destination(registry: Option<&EventRegistry>, option: &OptionContract) -> Pubkey {
+    registry
+        .map(|registry| registry.settlement_account)
+        .filter(|account| *account != Pubkey::default())
+        .unwrap_or(option.writer)
+}
+
+/// Strike an auto-exercise option prefunds at creation: strike × quantity.
+fn strike_escrow_for(params: &CreateOptionParams) -> Result<u64> {
+    if !params.auto_exercise {
+        return Ok(0);
+    }
+    params
+        .strike_lamports
+        .checked_mul(params.quantity as u64)
+        .ok_or(error!(QuorumError::MathOverflow))
+}
+
+/// Hand an auto-exercise option's prefunded strike back to the holder when
+/// it won't be exercised (cancel, event cancellation).
+fn refund_strike_escrow<'info>(
+    option: &mut Account<'info, OptionContract>,
+    holder: &AccountInfo<'info>,
+) -> Result<()> {
+    let amount = option.strike_escrowed;
+    if amount == 0 {
+        return Ok(());
+    }
+    option.strike_escrowed = 0;
+    transfer_from_pda(&option.to_account_info(), holder, amount)?;
+    emit_lamport_flow(&option.option_id, FlowKind::Refund, amount, option.key(), option.holder);
+    Ok(())
+}
+
+/// Shared body of freeze_option / unfreeze_option.
+fn set_option_frozen(ctx: Context<FreezeOption>, frozen: bool) -> Result<()> {
+    let signer = ctx.accounts.signer.key();
+    let option = &mut ctx.accounts.option_contract;
+    require!(
+        signer == ctx.accounts.config.authority || signer == option.writer,
+        QuorumError::UnauthorizedAuthority
+    );
+    require!(option.frozen != frozen, QuorumError::FreezeUnchanged);
+    option.frozen = frozen;
+
+    if frozen {
+        emit!(OptionFrozen {
+            option_id: option.option_id.clone(),
+            by: signer,
+        });
+    } else {
+        emit!(OptionUnfrozen {
+            option_id: option.option_id.clone(),
+            by: signer,
+        });
+    }
+
+    msg!("Option {}: {} by {}", if frozen { "frozen" } else { "unfrozen" }, option.option_id, signer);
+    Ok(())
+}
+
+/// Park an exercise's strike in the PDA until the venue confirms the tickets
+/// exist. Exercises by the same payer for the same co-holder stack onto one
+/// pending batch; anyone else waits until it is confirmed or refunded. The
+/// co-holder is kept apart from the payer, who may be a relayer or delegate.
+#[allow(clippy::too_many_arguments)]
+fn hold_for_issuance(
+    option: &mut OptionContract,
+    payer: Pubkey,
+    co_holder: Option<Pubkey>,
+    from_escrow: bool,
+    strike: u64,
+    royalty: u64,
+    fee: u64,
+    quantity: u8,
+    deadline: i64,
+) -> Result<()> {
+    require!(
+        option.pending_strike == 0
+            || (option.pending_payer == payer
+                && option.pending_co_holder == co_holder
+                && option.pending_from_escrow == from_escrow),
+        QuorumError::IssuancePending
+    );
+    // Royalty and fee both come out of the strike; the writer gets what's left
+    royalty
+        .checked_add(fee)
+        .filter(|cut| *cut <= strike)
+        .ok_or(QuorumError::MathOverflow)?;
+    option.pending_strike = option.pending_strike.checked_add(strike).ok_or(QuorumError::MathOverflow)?;
+    option.pending_royalty = option.pending_royalty.checked_add(royalty).ok_or(QuorumError::MathOverflow)?;
+    option.pending_fee = option.pending_fee.checked_add(fee).ok_or(QuorumError::MathOverflow)?;
+    option.pending_qty = option.pending_qty.checked_add(quantity).ok_or(QuorumError::MathOverflow)?;
+    option.pending_payer = payer;
+    option.pending_co_holder = co_holder;
+    option.pending_from_escrow = from_escrow;
+    option.issuance_deadline = deadline;
+    Ok(())
+}
+
+/// What confirm_issuance pays out of a pending batch: royalty to the venue,
+/// fee to the treasury, writer_share to the settlement account.
+struct PendingPayout {
+    strike: u64,
+    royalty: u64,
+    fee: u64,
+    writer_share: u64,
+    quantity: u8,
+    fully_exercised: bool,
+}
+
+/// confirm_issuance's effects, applied before a lamport moves: take the
+/// pending batch off the option, finishing it if no tickets remain. A second
+/// confirm of the same batch finds nothing pending.
+fn take_pending_issuance(option: &mut OptionContract) -> Result<PendingPayout> {
+    require!(option.pending_strike > 0, QuorumError::NotPendingIssuance);
+    let payout = PendingPayout {
+        strike: option.pending_strike,
+        royalty: option.pending_royalty,
+        fee: option.pending_fee,
+        writer_share: option
+            .pending_strike
+            .checked_sub(option.pending_royalty)
+            .and_then(|rest| rest.checked_sub(option.pending_fee))
+            .ok_or(QuorumError::MathOverflow)?,
+        quantity: option.pending_qty,
+        fully_exercised: option.status_enum()? == OptionStatus::PendingIssuance,
+    };
+    clear_pending_issuance(option);
+    if payout.fully_exercised {
+        option.set_status(OptionStatus::Exercised)?;
+    }
+    Ok(payout)
+}
+
+fn clear_pending_issuance(option: &mut OptionContract) {
+    option.pending_strike = 0;
+    option.pending_royalty = 0;
+    option.pending_fee = 0;
+    option.pending_qty = 0;
+    option.pending_payer = Pubkey::default();
+    option.pending_co_holder = None;
+    option.pending_from_escrow = false;
+    option.issuance_deadline = 0;
+}
+
+/// Record an exercise on the venue's fulfilment receipt. Partial exercises
+/// accumulate; the receipt outlives close_option.
+#[allow(clippy::too_many_arguments)]
+fn write_receipt(
+    receipt: &mut ExerciseReceipt,
+    option: &Account<OptionContract>,
+    bump: u8,
+    holder: Pubkey,
+    beneficiary: Pubkey,
+    quantity: u8,
+    strike_paid: u64,
+    now: i64,
+) -> Result<()> {
+    if receipt.option_created_at == 0 {
+        receipt.option = option.key();
+        receipt.option_id = option.option_id.clone();
+        receipt.option_created_at = option.created_at;
+        receipt.bump = bump;
+    }
+    // A reused option_id must not pile onto the old option's receipt
+    require!(
+        receipt.option_created_at == option.created_at,
+        QuorumError::ReceiptTaken
+    );
+    receipt.holder = holder;
+    receipt.beneficiary = beneficiary;
+    receipt.quantity = receipt.quantity.checked_add(quantity).ok_or(QuorumError::MathOverflow)?;
+    receipt.strike_paid = receipt.strike_paid.checked_add(strike_paid).ok_or(QuorumError::MathOverflow)?;
+    receipt.exercised_at = now;
+    Ok(())
+}
+
+/// Stamp identity onto a registry the first time the writer touches it.
+fn init_event_registry(registry: &mut EventRegistry, writer: Pubkey, event_hash: [u8; 32], bump: u8) {
+    if registry.writer == Pubkey::default() {
+        registry.writer = writer;
+        registry.event_hash = event_hash;
+        registry.bump = bump;
+    }
+}
+
+/// If the writer has registered ticket types for this event, ticket_type
+/// must be one of them. The registry is optional, so it comes in unchecked:
+/// we verify it's the right PDA and treat an empty account as "no registry".
+fn require_known_ticket_type(
+    registry_info: &AccountInfo,
+    writer: Pubkey,
+    event_name: &str,
+    ticket_type: &str,
+    program_id: &Pubkey,
+) -> Result<()> {
+    let Some(registry) = load_event_registry(registry_info, writer, event_name, program_id)? else {
+        return Ok(());
+    };
+    require!(
+        registry.ticket_types.is_empty()
+            || registry.ticket_types.iter().any(|t| t == ticket_type),
+        QuorumError::UnknownTicketType
+    );
+    Ok(())
+}
+
+/// An already-created option matches these params from this creator — the
+/// caller is retrying a create that landed. Every stored term is compared,
+/// defaults resolved as write_option_terms resolves them, except the
+/// premium: the stored one is net of the protocol fee.
+fn is_create_retry(option: &OptionContract, params: &CreateOptionParams, creator: Pubkey) -> bool {
+    let writer = params.writer.unwrap_or(creator);
+    option.status != OptionStatus::Reserved as u8
+        && option.creator == creator
+        && option.client_nonce == params.client_nonce
+        && option.option_id == params.option_id
+        && option.event_name == params.event_name
+        && option.event_date == params.event_date
+        && option.ticket_type == params.ticket_type
+        && option.quantity == params.quantity
+        && option.strike_lamports == params.strike_lamports
+        && option.expiry == params.expiry
+        && option.writer == writer
+        && option.venue == params.venue.unwrap_or(writer)
+        && option.venue_royalty_bps == params.venue_royalty_bps
+        && option.cancel_refund_bps == params.cancel_refund_bps
+        && option.exercise_window_start == params.exercise_window_start
+        && option.auto_exercise == params.auto_exercise
+        && option.transferable == params.transferable.unwrap_or(true)
+        && option.co_holders.len() == params.co_holders.len()
+        && option
+            .co_holders
+            .iter()
+            .zip(&params.co_holders)
+            .all(|(stored, asked)| stored.holder == asked.holder && stored.shares == asked.shares)
+        && option.metadata_uri == params.metadata_uri.as_deref().unwrap_or_default()
+        && option.identity_hash == params.identity_hash
+}
+
+/// If the writer pinned an event_expiry for this event, every new option for
+/// it expires then, whatever expiry the params asked for.
+fn apply_event_expiry(
+    registry_info: &AccountInfo,
+    writer: Pubkey,
+    params: &mut CreateOptionParams,
+    program_id: &Pubkey,
+) -> Result<()> {
+    let registry = load_event_registry(registry_info, writer, &params.event_name, program_id)?;
+    if let Some(event_expiry) = registry.map(|registry| registry.event_expiry).filter(|&expiry| expiry != 0) {
+        params.expiry = event_expiry;
+    }
+    Ok(())
+}
+
+/// Read the writer's EventRegistry for an event from an unchecked account,
+/// verifying its address. None if the writer never created one.
+fn load_event_registry(
+    registry_info: &AccountInfo,
+    writer: Pubkey,
+    event_name: &str,
+    program_id: &Pubkey,
+) -> Result<Option<EventRegistry>> {
+    let event_hash = event_seed(event_name);
+    let (expected, _) =
+        Pubkey::find_program_address(&[b"event", writer.as_ref(), &event_hash], program_id);
+    require_keys_eq!(registry_info.key(), expected, QuorumError::InvalidEventRegistry);
+    if registry_info.data_is_empty() {
+        return Ok(None);
+    }
+    require_keys_eq!(*registry_info.owner, *program_id, QuorumError::InvalidEventRegistry);
+
+    let data = registry_info.try_borrow_data()?;
+    Ok(Some(EventRegistry::try_deserialize(&mut &data[..])?))
+}
+
+/// If the writer set a lot_size for this event, a new option's quantity and
+/// every co-holder's share must come in whole lots.
+fn require_lot_terms(
+    registry_info: &AccountInfo,
+    writer: Pubkey,
+    params: &CreateOptionParams,
+    program_id: &Pubkey,
+) -> Result<()> {
+    let registry = load_event_registry(registry_info, writer, &params.event_name, program_id)?;
+    require_lot_multiple(registry.as_ref(), params.quantity)?;
+    for co_holder in &params.co_holders {
+        require_lot_multiple(registry.as_ref(), co_holder.shares)?;
+    }
+    Ok(())
+}
+
+/// `quantity` is a whole number of the event's lots; lot_size 0 or 1 (or
+/// no registry) allows anything.
+fn require_lot_multiple(registry: Option<&EventRegistry>, quantity: u8) -> Result<()> {
+    let lot_size = registry.map_or(0, |registry| registry.lot_size);
+    require!(lot_size <= 1 || quantity % lot_size == 0, QuorumError::InvalidLotSize);
+    Ok(())
+}
+
+/// The identity `venue` has bound to `wallet`, from that wallet's
+/// HolderIdentity account.
+fn require_identity(identity: Option<&HolderIdentity>, venue: Pubkey, wallet: Pubkey) -> Result<[u8; 32]> {
+    let identity = identity.ok_or(QuorumError::IdentityRequired)?;
+    require!(
+        identity.venue == venue && identity.wallet == wallet,
+        QuorumError::IdentityMismatch
+    );
+    Ok(identity.identity_hash)
+}
+
+/// Two options are interchangeable apart from quantity and premium: a
+/// merge of them exercises, expires, pays and refunds exactly as they would.
+fn same_terms(a: &OptionContract, b: &OptionContract) -> bool {
+    a.event_name == b.event_name
+        && a.event_date == b.event_date
+        && a.ticket_type == b.ticket_type
+        && a.strike_lamports == b.strike_lamports
+        && a.expiry == b.expiry
+        && a.exercise_window_start == b.exercise_window_start
+        && a.writer == b.writer
+        && a.venue == b.venue
+        && a.venue_royalty_bps == b.venue_royalty_bps
+        && a.cancel_refund_bps == b.cancel_refund_bps
+        && a.pool == b.pool
+        && a.auto_exercise == b.auto_exercise
+        && a.transferable == b.transferable
+        && a.identity_hash == b.identity_hash
+        && a.delegate == b.delegate
+}
+
+/// Count a new option against the writer's max_options for the event.
+/// Without a registry there's no cap and nothing to count.
+fn take_event_slot(registry_info: &AccountInfo, writer: Pubkey, event_name: &str, program_id: &Pubkey) -> Result<()> {
+    let Some(mut registry) = load_event_registry(registry_info, writer, event_name, program_id)? else {
+        return Ok(());
+    };
+    require!(
+        registry.max_options == 0 || registry.options_issued < registry.max_options,
+        QuorumError::EventSoldOut
+    );
+    registry.options_issued = registry.options_issued.checked_add(1).ok_or(QuorumError::MathOverflow)?;
+    let mut data = registry_info.try_borrow_mut_data()?;
+    registry.try_serialize(&mut &mut data[..])
+}
+
+/// Give a cancelled option's slot back to the event's cap.
+fn release_event_slot(registry_info: &AccountInfo, writer: Pubkey, event_name: &str, program_id: &Pubkey) -> Result<()> {
+    let Some(mut registry) = load_event_registry(registry_info, writer, event_name, program_id)? else {
+        return Ok(());
+    };
+    registry.options_issued = registry.options_issued.saturating_sub(1);
+    let mut data = registry_info.try_borrow_mut_data()?;
+    registry.try_serialize(&mut &mut data[..])
+}
+
+/// Re-derive an option PDA with find_program_address, check `key` is it and
+/// return the canonical bump. Creation stores only this bump, so every
+/// `bump = option_contract.bump` constraint later is pinned to the one
+/// canonical address — no grinding a second bump for the same seeds.
+fn canonical_option_bump(option_id: &str, client_nonce: u64, key: Pubkey, program_id: &Pubkey) -> Result<u8> {
+    let (expected, bump) = Pubkey::find_program_address(
+        &[b"option", OPTION_SEED_VERSION, option_id.as_bytes(), &client_nonce.to_le_bytes()],
+        program_id,
+    );
+    require_keys_eq!(key, expected, QuorumError::InvalidOptionAccount);
+    Ok(bump)
+}
+
+/// Stamp validated creation params onto a fresh option account.
+fn write_option_terms(
+    option: &mut OptionContract,
+    params: CreateOptionParams,
+    holder: Pubkey,
+    premium_mint: Option<Pubkey>,
+    pool: Option<Pubkey>,
+    now: i64,
+    bump: u8,
+) {
+    option.option_id = params.option_id;
+    option.event_name = params.event_name;
+    option.event_date = params.event_date;
+    option.ticket_type = params.ticket_type;
+    option.quantity = params.quantity;
+    option.premium_lamports = params.premium_lamports;
+    option.strike_lamports = params.strike_lamports;
+    option.holder = holder;
+    option.expiry = params.expiry;
+    option.status = OptionStatus::Active as u8;
+    option.created_at = now;
+    option.refund_decay_start = now;
+    option.venue_royalty_bps = params.venue_royalty_bps;
+    option.cancel_refund_bps = params.cancel_refund_bps;
+    option.bump = bump;
+    option.schema_version = OPTION_SCHEMA_VERSION;
+    option.client_nonce = params.client_nonce;
+    option.transferable = params.transferable.unwrap_or(true);
+    option.metadata_uri = params.metadata_uri.unwrap_or_default();
+    option.identity_hash = params.identity_hash;
+    option.premium_mint = premium_mint;
+    option.writer = params.writer.unwrap_or(holder);
+    option.venue = params.venue.unwrap_or(option.writer);
+    option.exercise_window_start = params.exercise_window_start;
+    option.creator = holder;
+    option.pool = pool;
+    option.co_holders = params.co_holders;
+    option.auto_exercise = params.auto_exercise;
+}
+
+/// Hold a fresh option account for a compound option's child: just enough
+/// for it to read as taken, so create_option can't claim the address.
+/// exercise_compound_option stamps the real terms over it.
+fn reserve_option(
+    option: &mut OptionContract,
+    underlying: &CreateOptionParams,
+    holder: Pubkey,
+    writer: Pubkey,
+    now: i64,
+    bump: u8,
+) {
+    option.option_id = underlying.option_id.clone();
+    option.client_nonce = underlying.client_nonce;
+    option.holder = holder;
+    option.creator = holder;
+    option.writer = writer;
+    option.status = OptionStatus::Reserved as u8;
+    option.created_at = now;
+    option.bump = bump;
+    option.schema_version = OPTION_SCHEMA_VERSION;
+}
+
+/// Count new live options against a holder, enforcing the per-holder cap
+/// (max_per_holder = 0 means unlimited). Stats may be freshly created.
+fn add_active_options(
+    stats: &mut HolderStats,
+    holder: Pubkey,
+    bump: u8,
+    config: &ProgramConfig,
+    count: u16,
+) -> Result<()> {
+    if stats.holder == Pubkey::default() {
+        stats.holder = holder;
+        stats.bump = bump;
+    }
+
+    let active = stats
+        .active_count
+        .checked_add(count)
+        .ok_or(QuorumError::HolderLimitReached)?;
+    require!(
+        config.max_per_holder == 0 || active <= config.max_per_holder,
+        QuorumError::HolderLimitReached
+    );
+    stats.active_count = active;
+    Ok(())
+}
+
+/// Wash-trade guard: a holder who just cancelled has to wait out
+/// config.action_cooldown_seconds before opening another option.
+fn require_cooldown_elapsed(stats: &HolderStats, config: &ProgramConfig, now: i64) -> Result<()> {
+    require!(
+        stats.last_action_ts == 0
+            || now >= stats.last_action_ts + config.action_cooldown_seconds as i64,
+        QuorumError::CooldownActive
+    );
+    Ok(())
+}
+
+/// An option left Active (exercised, expired, cancelled or transferred away).
+fn remove_active_option(stats: &mut HolderStats) {
+    stats.active_count = stats.active_count.saturating_sub(1);
+}
+
+/// Current Dutch-auction price: linear from premium_start at created_at
+/// down to premium_floor at decay_end, flat afterwards.
+fn dutch_premium(option: &OptionContract, now: i64) -> u64 {
+    if now >= option.decay_end {
+        return option.premium_floor;
+    }
+    let elapsed = (now - option.created_at).max(0) as u128;
+    let window = (option.decay_end - option.created_at) as u128;
+    let drop = (option.premium_start - option.premium_floor) as u128 * elapsed / window;
+    option.premium_start - drop as u64
+}
+
+/// Cancel refund, decaying linearly over the holder's time in the option:
+///
+///   refund = premium × cancel_refund_bps / 10_000
+///            × (expiry − now) / (expiry − refund_decay_start)
+///
+/// The full cancel_refund_bps right after the holder took the option,
+/// half of it halfway to expiry, nothing at expiry — the longer the writer
+/// carried the risk, the more of the premium it keeps.
+fn cancel_refund(option: &OptionContract, now: i64) -> u64 {
+    let life = option.expiry.saturating_sub(option.refund_decay_start);
+    if life <= 0 {
+        return 0;
+    }
+    let remaining = option.expiry.saturating_sub(now).clamp(0, life) as u128;
+    let ceiling = option.premium_lamports as u128 * option.cancel_refund_bps as u128 / 10_000;
+    (ceiling * remaining / life as u128) as u64
+}
+
+/// An option can only draw on a pool its own writer created.
+fn resolve_pool(pool: &Option<Account<OptionPool>>, writer: Pubkey) -> Result<Option<Pubkey>> {
+    match pool {
+        Some(pool) => {
+            require_keys_eq!(pool.writer, writer, QuorumError::InvalidPool);
+            Ok(Some(pool.key()))
+        }
+        None => Ok(None),
+    }
+}
+
+/// Pooled options share the writer's real ticket inventory: count an
+/// exercise of `quantity` tickets against the pool's total_cap.
+fn count_pool_exercise(pool: &mut Option<Account<OptionPool>>, option: &OptionContract, quantity: u8) -> Result<()> {
+    let Some(pool_key) = option.pool else {
+        return Ok(());
+    };
+    let pool = pool.as_mut().ok_or(QuorumError::InvalidPool)?;
+    require_keys_eq!(pool.key(), pool_key, QuorumError::InvalidPool);
+    let exercised = pool
+        .exercised_count
+        .checked_add(quantity as u32)
+        .ok_or(QuorumError::MathOverflow)?;
+    require!(exercised <= pool.total_cap, QuorumError::PoolCapReached);
+    pool.exercised_count = exercised;
+    Ok(())
+}
+
+fn emit_option_created(option: &OptionContract, protocol_fee: u64) {
+    emit!(OptionCreated {
+        option_id: option.option_id.clone(),
+        event_name: option.event_name.clone(),
+        holder: option.holder,
+        premium_lamports: option.premium_lamports,
+        expiry: option.expiry,
+        premium_mint: option.premium_mint,
+        ticket_type: option.ticket_type.clone(),
+        quantity: option.quantity,
+        venue_royalty_bps: option.venue_royalty_bps,
+        protocol_fee,
+        metadata_uri: option.metadata_uri.clone(),
+        premium_sol_display: match option.premium_mint {
+            Some(_) => 0,
+            None => option.premium_lamports / LAMPORTS_PER_MICRO_SOL,
+        },
+    });
+}
+
+/// Last second an exercise can land: expiry plus the configured grace
+/// period, so block-time jitter doesn't eat a well-timed exercise.
+/// Expiry only becomes crankable after this.
+fn exercise_deadline(option: &OptionContract, config: &ProgramConfig) -> i64 {
+    option.expiry.saturating_add(config.grace_seconds as i64)
+}
+
+/// NFT-backed options change hands with the token, so option.holder is
+/// only whoever held it at mint time. Paths that pay, refund, move or
+/// authorize by the holder field refuse them rather than act for a stale one.
+fn require_not_nft_backed(option: &OptionContract) -> Result<()> {
+    require!(option.nft_mint.is_none(), QuorumError::NftBacked);
+    Ok(())
+}
+
+/// Invariant for every path that pays an option's premium to the writer:
+/// the holder can no longer exercise it or be refunded from it, and nothing
+/// the holder prefunded or paid toward unconfirmed tickets is still sitting
+/// in the PDA. Checked even where the
+/// caller's own status checks already imply it, so a new payout path can't
+/// quietly skip it.
+fn require_writer_payout_allowed(option: &OptionContract) -> Result<()> {
+    let released = match option.status_enum()? {
+        OptionStatus::Expired | OptionStatus::Exercised | OptionStatus::Cancelled => true,
+        // Never claimed, so no holder premium was ever paid in
+        OptionStatus::Listed => option.premium_lamports == 0,
+        OptionStatus::Active | OptionStatus::PendingIssuance | OptionStatus::Reserved => false,
+    };
+    require!(released, QuorumError::PremiumLocked);
+    require!(option.strike_escrowed == 0, QuorumError::PremiumLocked);
+    // A strike awaiting issuance goes to the writer or back to the payer, never both
+    require!(option.pending_strike == 0, QuorumError::IssuancePending);
+    require!(!option.frozen, QuorumError::OptionFrozen);
+    Ok(())
+}
+
+/// Move whatever has vested since the last update from locked to unlocked.
+/// locked vests linearly from last_update to vesting_end.
+fn vest_writer_vault(vault: &mut WriterVault, now: i64) {
+    if now >= vault.vesting_end {
+        vault.unlocked = vault.unlocked.saturating_add(vault.locked);
+        vault.locked = 0;
+    } else if now > vault.last_update {
+        let elapsed = (now - vault.last_update) as u128;
+        let window = (vault.vesting_end - vault.last_update) as u128;
+        let vested = (vault.locked as u128 * elapsed / window) as u64;
+        vault.unlocked = vault.unlocked.saturating_add(vested);
+        vault.locked -= vested;
+    }
+    vault.last_update = now.max(vault.last_update);
+}
+
+/// Add a premium to the vault's locked balance. Everything still locked
+/// vests toward the later of the current vesting_end and now + the config's
+/// vesting period, so a deposit never speeds up an earlier one.
+fn deposit_writer_vault(vault: &mut WriterVault, amount: u64, config: &ProgramConfig, now: i64) -> Result<()> {
+    vest_writer_vault(vault, now);
+    vault.locked = vault.locked.checked_add(amount).ok_or(QuorumError::MathOverflow)?;
+    vault.vesting_end = vault.vesting_end.max(now + config.writer_vesting_seconds as i64);
+    Ok(())
+}
+
+/// Last moment a holder can dispute an expired option.
+fn dispute_deadline(option: &OptionContract, config: &ProgramConfig) -> i64 {
+    exercise_deadline(option, config).saturating_add(config.dispute_window_seconds as i64)
+}
+
+/// The writer can't take an expired option's premium while a dispute is
+/// open or could still be raised.
+fn require_dispute_settled(option: &OptionContract, config: &ProgramConfig, now: i64) -> Result<()> {
+    require!(!option.disputed, QuorumError::DisputeOpen);
+    require!(now > dispute_deadline(option, config), QuorumError::DisputeWindowActive);
+    Ok(())
+}
+
+/// Fold one option's premium / face value ratio into the event's demand
+/// score. The first option sets it outright; after that it's an exponential
+/// moving average. All in u128 and rounded to nearest, so small ratios
+/// don't truncate away. Free tickets have no ratio and are skipped.
+fn record_demand(stats: &mut EventStats, premium: u64, strike: u64, quantity: u8) {
+    let face_value = strike as u128 * quantity as u128;
+    if face_value == 0 {
+        return;
+    }
+    let ratio = (premium as u128 * DEMAND_SCORE_SCALE as u128 + face_value / 2) / face_value;
+    let window = DEMAND_SCORE_WINDOW as u128;
+    let score = if stats.demand_samples == 0 {
+        ratio
+    } else {
+        (stats.demand_score as u128 * (window - 1) + ratio + window / 2) / window
+    };
+    stats.demand_score = score.min(u64::MAX as u128) as u64;
+    stats.demand_samples = stats.demand_samples.saturating_add(1);
+}
+
+/// Write one (timestamp, premium) sample into the event's price history.
+/// The buffer holds the last PRICE_HISTORY_LEN samples: price_history_head
+/// is the oldest once it's full, and each write overwrites it.
+fn record_price(stats: &mut EventStats, now: i64, premium: u64) {
+    let head = stats.price_history_head as usize % PRICE_HISTORY_LEN;
+    stats.price_history[head] = PriceObservation { timestamp: now, premium };
+    stats.price_history_head = ((head + 1) % PRICE_HISTORY_LEN) as u8;
+    stats.price_history_count = stats.price_history_count.saturating_add(1).min(PRICE_HISTORY_LEN as u8);
+}
+
+/// Apply `update` to the event's EventStats PDA, creating it on first use.
+/// It's passed unchecked because its seed is derived from the option's
+/// event name, so we verify the address here instead of in the context.
+fn record_event_stats<'info>(
+    stats_info: &AccountInfo<'info>,
+    event_name: &str,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    program_id: &Pubkey,
+    update: impl FnOnce(&mut EventStats),
+) -> Result<()> {
+    let event_hash = event_seed(event_name);
+    let (expected, bump) = Pubkey::find_program_address(&[b"stats", &event_hash], program_id);
+    require_keys_eq!(stats_info.key(), expected, QuorumError::InvalidEventStats);
+
+    let mut stats = if stats_info.data_is_empty() {
+        let seeds: &[&[u8]] = &[b"stats", &event_hash, &[bump]];
+        create_pda_account(
+            stats_info,
+            payer,
+            system_program,
+            EventStats::MAX_SIZE,
+            seeds,
+            program_id,
+        )?;
+        EventStats {
+            event_hash,
+            bump,
+            ..Default::default()
+        }
+    } else {
+        require_keys_eq!(*stats_info.owner, *program_id, QuorumError::InvalidEventStats);
+        // Stats written before a field was appended grow on first touch; the
+        // zeroed tail reads as the new fields' defaults
+        if stats_info.data_len() < EventStats::MAX_SIZE {
+            let rent_due = Rent::get()?
+                .minimum_balance(EventStats::MAX_SIZE)
+                .saturating_sub(stats_info.lamports());
+            if rent_due > 0 {
+                system_program::transfer(
+                    CpiContext::new(
+                        system_program.clone(),
+                        system_program::Transfer {
+                            from: payer.clone(),
+                            to: stats_info.clone(),
+                        },
+                    ),
+                    rent_due,
+                )?;
+            }
+            stats_info.realloc(EventStats::MAX_SIZE, true)?;
+        }
+        let data = stats_info.try_borrow_data()?;
+        EventStats::try_deserialize(&mut &data[..])?
+    };
+
+    update(&mut stats);
+    let mut data = stats_info.try_borrow_mut_data()?;
+    stats.try_serialize(&mut &mut data[..])
+}
+
+/// Append an option to its event's EventIndex PDA, creating it on first use.
+/// The account grows one slot per option; payer covers the extra rent.
+/// Indexing is best-effort: once MAX_INDEXED_OPTIONS are listed the option
+/// is left out rather than failing the create — the index is keyed by event
+/// name alone, so anyone filling it would otherwise block every venue.
+fn index_option<'info>(
+    index_info: &AccountInfo<'info>,
+    event_name: &str,
+    option_key: Pubkey,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    program_id: &Pubkey,
+) -> Result<()> {
+    let event_hash = event_seed(event_name);
+    let (expected, bump) = Pubkey::find_program_address(&[b"index", &event_hash], program_id);
+    require_keys_eq!(index_info.key(), expected, QuorumError::InvalidEventIndex);
+
+    let mut index = if index_info.data_is_empty() {
+        let seeds: &[&[u8]] = &[b"index", &event_hash, &[bump]];
+        create_pda_account(
+            index_info,
+            payer,
+            system_program,
+            EventIndex::space(0),
+            seeds,
+            program_id,
+        )?;
+        EventIndex {
+            event_hash,
+            bump,
+            options: Vec::new(),
+        }
+    } else {
+        require_keys_eq!(*index_info.owner, *program_id, QuorumError::InvalidEventIndex);
+        let data = index_info.try_borrow_data()?;
+        EventIndex::try_deserialize(&mut &data[..])?
+    };
+
+    if index.options.len() >= MAX_INDEXED_OPTIONS {
+        msg!("Event index full — option {} not indexed", option_key);
+        return Ok(());
+    }
+    index.options.push(option_key);
+
+    let new_size = EventIndex::space(index.options.len());
+    let rent_due = Rent::get()?
+        .minimum_balance(new_size)
+        .saturating_sub(index_info.lamports());
+    if rent_due > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.clone(),
+                system_program::Transfer {
+                    from: payer.clone(),
+                    to: index_info.clone(),
+                },
+            ),
+            rent_due,
+        )?;
+    }
+    index_info.realloc(new_size, false)?;
+    let mut data = index_info.try_borrow_mut_data()?;
+    index.try_serialize(&mut &mut data[..])
+}
+
+/// Drop an option from its event's EventIndex and shrink the account,
+/// returning the freed rent to `refund_to`. Options created before the
+/// index existed simply aren't found.
+fn unindex_option(
+    index_info: &AccountInfo,
+    event_name: &str,
+    option_key: Pubkey,
+    refund_to: &AccountInfo,
+    program_id: &Pubkey,
+) -> Result<()> {
+    let event_hash = event_seed(event_name);
+    let (expected, _) = Pubkey::find_program_address(&[b"index", &event_hash], program_id);
+    require_keys_eq!(index_info.key(), expected, QuorumError::InvalidEventIndex);
+    if index_info.data_is_empty() {
+        return Ok(());
+    }
+    require_keys_eq!(*index_info.owner, *program_id, QuorumError::InvalidEventIndex);
+
+    let mut index = {
+        let data = index_info.try_borrow_data()?;
+        EventIndex::try_deserialize(&mut &data[..])?
+    };
+    let Some(position) = index.options.iter().position(|key| *key == option_key) else {
+        return Ok(());
+    };
+    index.options.swap_remove(position);
+
+    let new_size = EventIndex::space(index.options.len());
+    {
+        let mut data = index_info.try_borrow_mut_data()?;
+        index.try_serialize(&mut &mut data[..])?;
+    }
+    index_info.realloc(new_size, false)?;
+    let freed = withdrawable_lamports(index_info)?;
+    if freed > 0 {
+        transfer_from_pda(index_info, refund_to, freed)?;
+    }
+    Ok(())
+}
+
+/// Active → Expired bookkeeping shared by expire_option and the batch sweep.
+/// The premium is retained in full; keepers are paid by pay_keeper_reward.
+fn settle_expiry(option: &mut OptionContract, stats: &mut HolderStats, global: &mut GlobalStats) -> Result<()> {
+    option.set_status(OptionStatus::Expired)?;
+    remove_active_option(stats);
+    global.record_expired();
+    Ok(())
+}
+
+/// Pay the expiring keeper config.expire_reward_lamports out of the
+/// KeeperTreasury. No treasury, or too little in it, means no reward rather
+/// than a failed expiry. Returns what was paid.
+fn pay_keeper_reward<'info>(
+    treasury: Option<&mut Account<'info, KeeperTreasury>>,
+    keeper: &AccountInfo<'info>,
+    config: &ProgramConfig,
+    option_id: &str,
+) -> Result<u64> {
+    let reward = config.expire_reward_lamports;
+    let Some(treasury) = treasury else {
+        return Ok(0);
+    };
+    if reward == 0 || treasury.balance < reward {
+        return Ok(0);
+    }
+
+    let treasury_info = treasury.to_account_info();
+    transfer_from_pda(&treasury_info, keeper, reward)?;
+    treasury.balance = treasury.balance.checked_sub(reward).ok_or(QuorumError::MathOverflow)?;
+    treasury.total_paid = treasury.total_paid.checked_add(reward).ok_or(QuorumError::MathOverflow)?;
+
+    emit_lamport_flow(option_id, FlowKind::KeeperReward, reward, treasury_info.key(), keeper.key());
+    Ok(reward)
+}
+
+fn emit_option_expired(option: &OptionContract, keeper: Pubkey, keeper_reward: u64, now: i64) {
+    emit!(OptionExpired {
+        option_id: option.option_id.clone(),
+        holder: option.holder,
+        premium_lamports: option.premium_lamports,
+        premium_mint: option.premium_mint,
+        keeper,
+        keeper_reward,
+    });
+    emit_status_changed(option, OptionStatus::Active as u8, now);
+}
+
+/// Uniform lifecycle event, emitted alongside the transition-specific one
+/// so indexers can follow every status change through a single event type.
+fn emit_status_changed(option: &OptionContract, from_status: u8, timestamp: i64) {
+    emit!(OptionStatusChanged {
+        option_id: option.option_id.clone(),
+        from_status,
+        to_status: option.status,
+        timestamp,
+    });
+}
+
+/// One SOL movement for the LamportFlow ledger; zero amounts aren't logged.
+/// SPL premiums move as tokens and are left to the token program's records.
+fn emit_lamport_flow(option_id: &str, kind: FlowKind, amount: u64, from: Pubkey, to: Pubkey) {
+    if amount > 0 {
+        emit!(LamportFlow {
+            option_id: option_id.to_string(),
+            kind: kind as u8,
+            amount,
+            from,
+            to,
+        });
+    }
+}
+
+/// Protocol's cut of an incoming premium.
+fn protocol_fee(config: &ProgramConfig, premium: u64) -> u64 {
+    (premium as u128 * config.protocol_fee_bps as u128 / 10_000) as u64
+}
+
+/// Protocol's cut of an exercised strike, independent of the venue royalty.
+fn exercise_fee(config: &ProgramConfig, strike: u64) -> u64 {
+    (strike as u128 * config.exercise_fee_bps as u128 / 10_000) as u64
+}
+
+/// Shared Listed → Active claim for claim_dutch_option and
+/// claim_presale_option; allowlist checks happen before this.
+fn process_claim(ctx: Context<ClaimDutchOption>) -> Result<()> {
+    require!(!ctx.accounts.config.paused, QuorumError::ProgramPaused);
+
+    let option = &ctx.accounts.option_contract;
+    require!(option.status_enum()? == OptionStatus::Listed, QuorumError::NotListed);
+
+    let now = current_timestamp(&ctx.accounts.clock)?;
+    require!(now <= option.expiry, QuorumError::OptionExpired);
+
+    let holder = ctx.accounts.holder.key();
+    let price = dutch_premium(option, now);
+    let fee = protocol_fee(&ctx.accounts.config, price);
+    let premium = price.checked_sub(fee).ok_or(QuorumError::MathOverflow)?;
+
+    require_cooldown_elapsed(
+        &ctx.accounts.holder_stats,
+        &ctx.accounts.config,
+        now,
+    )?;
+    add_active_options(
+        &mut ctx.accounts.holder_stats,
+        holder,
+        ctx.bumps.holder_stats,
+        &ctx.accounts.config,
+        1,
+    )?;
+
+    // Effects before interactions
+    let option = &mut ctx.accounts.option_contract;
+    option.holder = holder;
+    option.premium_lamports = premium;
+    option.set_status(OptionStatus::Active)?;
+    // The holder's risk starts now, not when the listing went up
+    option.refund_decay_start = now;
+
+    if fee > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.holder.to_account_info(),
+                    to: ctx.accounts.treasury.to_account_info(),
+                },
+            ),
+            fee,
+        )?;
+    }
+    system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.holder.to_account_info(),
+                to: ctx.accounts.option_contract.to_account_info(),
+            },
+        ),
+        premium,
+    )?;
+
+    let option = &ctx.accounts.option_contract;
+    emit_lamport_flow(&option.option_id, FlowKind::Fee, fee, holder, ctx.accounts.treasury.key());
+    emit_lamport_flow(&option.option_id, FlowKind::Premium, premium, holder, option.key());
+    record_event_stats(
+        &ctx.accounts.event_stats,
+        &option.event_name,
+        &ctx.accounts.holder,
+        &ctx.accounts.system_program,
+        ctx.program_id,
+        |stats| {
+            stats.total_options_created += 1;
+            stats.total_premium_collected = stats.total_premium_collected.saturating_add(premium);
+            record_demand(stats, premium, option.strike_lamports, option.quantity);
+            record_price(stats, now, premium);
+        },
+    )?;
+    ctx.accounts.global_stats.record_created(1, premium);
+    emit_option_created(option, fee);
+    emit_status_changed(option, OptionStatus::Listed as u8, now);
+
+    msg!("Dutch option claimed: {} by {} at {} lamports",
+         option.option_id, holder, price);
+    Ok(())
+}
+
+/// Gate claims on the writer's presale allowlist for the event, if it has
+/// one. Leaves are sha256(claimant pubkey); pairs hash in sorted order.
+fn require_presale_access(
+    registry_info: &AccountInfo,
+    option: &OptionContract,
+    claimant: Pubkey,
+    proof: Option<&[[u8; 32]]>,
+    program_id: &Pubkey,
+) -> Result<()> {
+    let registry = load_event_registry(registry_info, option.writer, &option.event_name, program_id)?;
+    let Some(root) = registry.and_then(|registry| registry.presale_root) else {
+        return Ok(());
+    };
+    let proof = proof.ok_or(QuorumError::NotOnAllowlist)?;
+    require!(proof.len() <= MAX_PROOF_LEN, QuorumError::NotOnAllowlist);
+
+    let leaf = anchor_lang::solana_program::hash::hash(claimant.as_ref()).to_bytes();
+    let computed = proof.iter().fold(leaf, |node, sibling| {
+        let (left, right) = if node <= *sibling { (node, *sibling) } else { (*sibling, node) };
+        anchor_lang::solana_program::hash::hashv(&[&left, &right]).to_bytes()
+    });
+    require!(computed == root, QuorumError::NotOnAllowlist);
+    Ok(())
+}
+
+/// Shared exercise path: pay strike for `exercise_qty` tickets into the
+/// option PDA and decrement the remaining quantity, flipping to
+/// PendingIssuance at zero. The strike is held until confirm_issuance.
+/// Fails with SlippageExceeded if the total would top `max_total_cost`.
+/// Tickets go to `beneficiary` if given, else to the holder.
+fn process_exercise(
+    ctx: Context<ExerciseOption>,
+    exercise_qty: u8,
+    max_total_cost: u64,
+    beneficiary: Option<Pubkey>,
+    relayed_for: Option<Pubkey>,
+) -> Result<ExerciseResult> {
+    let option = &ctx.accounts.option_contract;
+
+    require!(option.status_enum()? == OptionStatus::Active, QuorumError::NotActive);
+    require!(!option.frozen, QuorumError::OptionFrozen);
+    // Whoever pays, and whose authority exercises — the same wallet unless a
+    // relayer submitted a signature-verified exercise for the fan
+    let paid_by = ctx.accounts.holder.key();
+    let signer = relayed_for.unwrap_or(paid_by);
+    // Co-held options are exercised share by share, each wallet for itself
+    let co_holder_index = option.co_holders.iter().position(|c| c.holder == signer);
+    match option.nft_mint {
+        // Whoever holds the option NFT is the holder
+        Some(nft_mint) => {
+            let holder_nft = ctx.accounts.holder_nft.as_ref().ok_or(QuorumError::InvalidNftAccount)?;
+            require_keys_eq!(holder_nft.mint, nft_mint, QuorumError::InvalidNftAccount);
+            require_keys_eq!(holder_nft.owner, signer, QuorumError::UnauthorizedHolder);
+            require!(holder_nft.amount == 1, QuorumError::UnauthorizedHolder);
+        }
+        None if !option.co_holders.is_empty() => {
+            let index = co_holder_index.ok_or(QuorumError::UnauthorizedHolder)?;
+            require!(
+                exercise_qty <= option.co_holders[index].shares,
+                QuorumError::InvalidExerciseQuantity
+            );
+        }
+        None => require!(
+            signer == option.holder || Some(signer) == option.delegate,
+            QuorumError::UnauthorizedHolder
+        ),
+    }
+    // KYC-gated: the exercising wallet must be bound to the option's identity
+    if let Some(identity_hash) = option.identity_hash {
+        let identity = require_identity(ctx.accounts.holder_identity.as_deref(), option.venue, signer)?;
+        require!(identity == identity_hash, QuorumError::IdentityMismatch);
+    }
+    require!(
+        exercise_qty > 0 && exercise_qty <= option.quantity,
+        QuorumError::InvalidExerciseQuantity
+    );
+
+    let now = current_timestamp(&ctx.accounts.clock)?;
+    require!(
+        now <= exercise_deadline(option, &ctx.accounts.config),
+        QuorumError::OptionExpired
+    );
+    require!(
+        now >= option.exercise_window_start,
+        QuorumError::TooEarlyToExercise
+    );
+
+    // Royalty comes out of the strike, so the holder's total outlay is the strike itself
+    let strike_paid = option
+        .strike_lamports
+        .checked_mul(exercise_qty as u64)
+        .ok_or(QuorumError::MathOverflow)?;
+    let registry = load_event_registry(
+        &ctx.accounts.event_registry,
+        option.writer,
+        &option.event_name,
+        ctx.program_id,
+    )?;
+    // Partial exercises come in whole lots; taking everything left is always
+    // allowed, so options written before a lot_size was set aren't stranded
+    let whole = co_holder_index.map_or(option.quantity, |index| option.co_holders[index].shares);
+    if exercise_qty != whole {
+        require_lot_multiple(registry.as_ref(), exercise_qty)?;
+    }
+    let royalty_bps = effective_royalty_bps(registry.as_ref(), option);
+    let royalty = (strike_paid as u128 * royalty_bps as u128 / 10_000) as u64;
+    let fee = exercise_fee(&ctx.accounts.config, strike_paid);
+    // Exercising in the grace window costs a penalty on top, held and paid
+    // out with the strike remainder to the event's settlement account
+    let penalty = late_exercise_penalty(&ctx.accounts.config, option, strike_paid, now);
+    let owed = strike_paid.checked_add(penalty).ok_or(QuorumError::MathOverflow)?;
+    require!(owed <= max_total_cost, QuorumError::SlippageExceeded);
+
+    count_pool_exercise(&mut ctx.accounts.pool, option, exercise_qty)?;
+
+    // Effects first: flip state before any lamports move, so a failed or
+    // re-entered transfer can never leave the option exercisable twice
+    let option = &mut ctx.accounts.option_contract;
+    option.quantity -= exercise_qty;
+    option.exercised_qty += exercise_qty;
+    // The NFT may have traded since minting — record who actually exercised
+    if option.nft_mint.is_some() {
+        option.holder = signer;
+    }
+    let exerciser = match co_holder_index {
+        Some(index) => {
+            option.co_holders[index].shares -= exercise_qty;
+            signer
+        }
+        _ => option.holder,
+    };
+    if option.quantity == 0 {
+        option.set_status(OptionStatus::PendingIssuance)?;
+        remove_active_option(&mut ctx.accounts.holder_stats);
+        ctx.accounts.global_stats.remove_active(1);
+    }
+    // An auto-exercise option already paid its strike into the PDA
+    let prefunded = option.strike_escrowed > 0;
+    if prefunded {
+        option.strike_escrowed = option
+            .strike_escrowed
+            .checked_sub(strike_paid)
+            .ok_or(QuorumError::MathOverflow)?;
+    }
+    let issuance_deadline = now
+        .checked_add(ctx.accounts.config.issuance_timeout_seconds as i64)
+        .ok_or(QuorumError::MathOverflow)?;
+    // A failed issuance refunds whoever's lamports these were
+    let payer = if prefunded { exerciser } else { paid_by };
+    let co_holder = co_holder_index.map(|_| signer);
+    hold_for_issuance(option, payer, co_holder, prefunded, owed, royalty, fee, exercise_qty, issuance_deadline)?;
+
+    write_receipt(
+        &mut ctx.accounts.receipt,
+        option,
+        ctx.bumps.receipt,
+        exerciser,
+        beneficiary.unwrap_or(exerciser),
+        exercise_qty,
+        strike_paid,
+        now,
+    )?;
+
+    // Interactions: the strike waits in the PDA (or its strike escrow) until
+    // the venue confirms issuance
+    if let Some(mint) = option.strike_mint {
+        let (escrow, holder_token, token_program) = unpack_spl_accounts(
+            &ctx.accounts.strike_escrow,
+            &ctx.accounts.holder_strike_token,
+            &ctx.accounts.token_program,
+        )?;
+        require_keys_eq!(holder_token.mint, mint, QuorumError::InvalidRecipient);
+        token::transfer(
+            CpiContext::new(
+                token_program.to_account_info(),
+                token::Transfer {
+                    from: holder_token.to_account_info(),
+                    to: escrow.to_account_info(),
+                    authority: ctx.accounts.holder.to_account_info(),
+                },
+            ),
+            owed,
+        )?;
+    } else if !prefunded && owed > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.holder.to_account_info(),
+                    to: option.to_account_info(),
+                },
+            ),
+            owed,
+        )?;
+        emit_lamport_flow(&option.option_id, FlowKind::StrikeEscrow, owed, paid_by, option.key());
+    }
+
+    emit_exercised(option, strike_paid, exercise_qty, now);
+    emit_late_exercise_penalty(option, penalty);
+
+    msg!("Option exercised: {} by {} — {} tickets, strike: {} lamports, {} remaining",
+         option.option_id, option.holder, exercise_qty, strike_paid, option.quantity);
+    Ok(ExerciseResult {
+        exercised_qty: exercise_qty,
+        strike_paid,
+        status: option.status,
+        remaining_qty: option.quantity,
+    })
+}
+
+/// config.late_exercise_penalty_bps of the strike, owed for exercising
+/// after expiry but inside the grace window. A prefunded auto-exercise
+/// strike was paid on time, so it owes nothing.
+fn late_exercise_penalty(config: &ProgramConfig, option: &OptionContract, strike: u64, now: i64) -> u64 {
+    if now <= option.expiry || option.strike_escrowed > 0 {
+        return 0;
+    }
+    (strike as u128 * config.late_exercise_penalty_bps as u128 / 10_000) as u64
+}
+
+fn emit_late_exercise_penalty(option: &OptionContract, penalty: u64) {
+    if penalty > 0 {
+        emit!(LateExercisePenalty {
+            option_id: option.option_id.clone(),
+            holder: option.holder,
+            writer: option.writer,
+            penalty,
+        });
+    }
+}
+
+/// OptionExercised once the last ticket is taken, OptionPartiallyExercised
+/// while some remain.
+fn emit_exercised(option: &OptionContract, strike_paid: u64, exercise_qty: u8, now: i64) {
+    if option.quantity == 0 {
+        emit!(OptionExercised {
+            option_id: option.option_id.clone(),
+            holder: option.holder,
+            strike_lamports: strike_paid,
+            quantity: exercise_qty,
+        });
+        emit_status_changed(option, OptionStatus::Active as u8, now);
+    } else {
+        emit!(OptionPartiallyExercised {
+            option_id: option.option_id.clone(),
+            holder: option.holder,
+            strike_lamports: strike_paid,
+            exercised_qty: exercise_qty,
+            remaining_qty: option.quantity,
+        });
+    }
+}
+
+/// Require that the instruction just before this one is the ed25519 program
+/// verifying `message` under `signer`, with key, signature and message all
+/// inline in that instruction. The ed25519 program already checked the
+/// signature itself; this checks it signed what we think it signed.
+fn verify_ed25519_signature(instructions: &AccountInfo, signer: &Pubkey, message: &[u8]) -> Result<()> {
+    let current = load_current_index_checked(instructions)? as usize;
+    require!(current > 0, QuorumError::InvalidQuoteSignature);
+    let ix = load_instruction_at_checked(current - 1, instructions)?;
+    require_keys_eq!(ix.program_id, ed25519_program::ID, QuorumError::InvalidQuoteSignature);
+
+    // Layout: signature count, padding, then one 14-byte offsets record
+    let data = &ix.data;
+    require!(data.len() >= 16 && data[0] == 1, QuorumError::InvalidQuoteSignature);
+    let read_u16 = |at: usize| u16::from_le_bytes([data[at], data[at + 1]]) as usize;
+    // Instruction index u16::MAX means "this instruction"
+    require!(
+        read_u16(4) == u16::MAX as usize
+            && read_u16(8) == u16::MAX as usize
+            && read_u16(14) == u16::MAX as usize,
+        QuorumError::InvalidQuoteSignature
+    );
+    let (key_offset, message_offset, message_len) = (read_u16(6), read_u16(10), read_u16(12));
+    let key = data
+        .get(key_offset..key_offset + 32)
+        .ok_or(QuorumError::InvalidQuoteSignature)?;
+    let signed = data
+        .get(message_offset..message_offset + message_len)
+        .ok_or(QuorumError::InvalidQuoteSignature)?;
+    require!(
+        key == signer.as_ref() && signed == message,
+        QuorumError::InvalidQuoteSignature
+    );
+    Ok(())
+}
+
+/// Current unix time for every time-gated check. Each such instruction
+/// takes an optional `clock` account — test harnesses serve a warped clock
+/// through it — and without one the time comes from Clock::get().
+fn current_timestamp(clock: &Option<Sysvar<Clock>>) -> Result<i64> {
+    match clock {
+        Some(clock) => Ok(clock.unix_timestamp),
+        None => Ok(Clock::get()?.unix_timestamp),
+    }
+}
+
+/// Burn a signed message's nonce by creating its QuoteNonce PDA at
+/// [seed, authority, nonce]; an existing one means the message was already
+/// used. Quotes key on the venue, relayed exercises on the fan.
+fn consume_nonce<'info>(
+    nonce_info: &AccountInfo<'info>,
+    seed: &[u8],
+    authority: Pubkey,
+    nonce: u64,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    program_id: &Pubkey,
+    now: i64,
+) -> Result<()> {
+    let nonce_bytes = nonce.to_le_bytes();
+    let (expected, bump) =
+        Pubkey::find_program_address(&[seed, authority.as_ref(), &nonce_bytes], program_id);
+    require_keys_eq!(nonce_info.key(), expected, QuorumError::InvalidNonceAccount);
+    require!(nonce_info.data_is_empty(), QuorumError::QuoteReplayed);
+
+    let seeds: &[&[u8]] = &[seed, authority.as_ref(), &nonce_bytes, &[bump]];
+    create_pda_account(
+        nonce_info,
+        payer,
+        system_program,
+        QuoteNonce::MAX_SIZE,
+        seeds,
+        program_id,
+    )?;
+    let record = QuoteNonce {
+        venue: authority,
+        nonce,
+        used_at: now,
+        bump,
+    };
+    let mut data = nonce_info.try_borrow_mut_data()?;
+    record.try_serialize(&mut &mut data[..])
+}
+
+/// Create a program-owned PDA the way Anchor's `init` does, for accounts we
+/// can't declare in a context. create_account fails on an address that
+/// already holds lamports, so a pre-funded one — anyone can send lamports to
+/// a predictable PDA — is topped up to rent, then allocated and assigned.
+fn create_pda_account<'info>(
+    target: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    space: usize,
+    seeds: &[&[u8]],
+    program_id: &Pubkey,
+) -> Result<()> {
+    let rent = Rent::get()?.minimum_balance(space);
+    let current = target.lamports();
+    if current == 0 {
+        return system_program::create_account(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                system_program::CreateAccount {
+                    from: payer.clone(),
+                    to: target.clone(),
+                },
+                &[seeds],
+            ),
+            rent,
+            space as u64,
+            program_id,
+        );
+    }
+    let top_up = rent.saturating_sub(current);
+    if top_up > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.clone(),
+                system_program::Transfer {
+                    from: payer.clone(),
+                    to: target.clone(),
+                },
+            ),
+            top_up,
+        )?;
+    }
+    system_program::allocate(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            system_program::Allocate {
+                account_to_allocate: target.clone(),
+            },
+            &[seeds],
+        ),
+        space as u64,
+    )?;
+    system_program::assign(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            system_program::Assign {
+                account_to_assign: target.clone(),
+            },
+            &[seeds],
+        ),
+        program_id,
+    )
+}
+
+/// Close a program-owned account we can't close through a constraint:
+/// every remaining lamport to `rent_to`, then hand it back to the system
+/// program with no data.
+fn close_program_account(info: &AccountInfo, rent_to: &AccountInfo) -> Result<()> {
+    let rent = info.lamports();
+    **info.try_borrow_mut_lamports()? = 0;
+    let credited = rent_to.lamports().checked_add(rent).ok_or(QuorumError::MathOverflow)?;
+    **rent_to.try_borrow_mut_lamports()? = credited;
+    info.assign(&System::id());
+    info.realloc(0, false)?;
+    Ok(())
+}
+
+/// Lamports a program-owned account can give up without dropping below
+/// rent exemption at its current size. Premiums, escrowed bids and rent
+/// share one balance, so this is the only safe measure of what's spendable.
+fn withdrawable_lamports(account: &AccountInfo) -> Result<u64> {
+    let rent_floor = Rent::get()?.minimum_balance(account.data_len());
+    Ok(account.lamports().saturating_sub(rent_floor))
+}
+
+/// Move lamports out of a program-owned PDA.
+/// system_program::transfer only works on system-owned accounts, so we
+/// debit/credit the balances directly. Never dips into the rent reserve.
+fn transfer_from_pda(from: &AccountInfo, to: &AccountInfo, amount: u64) -> Result<()> {
+    require!(amount <= withdrawable_lamports(from)?, QuorumError::InsufficientWithdrawable);
+    let debited = from.lamports().checked_sub(amount).ok_or(QuorumError::MathOverflow)?;
+    **from.try_borrow_mut_lamports()? = debited;
+    let credited = to.lamports().checked_add(amount).ok_or(QuorumError::MathOverflow)?;
+    **to.try_borrow_mut_lamports()? = credited;
+    Ok(())
+}
+
+/// Move SPL premium tokens out of an option's escrow, signed by the option PDA.
+fn transfer_from_escrow<'info>(
+    option: &Account<'info, OptionContract>,
+    escrow: &Account<'info, TokenAccount>,
+    to: &Account<'info, TokenAccount>,
+    token_program: &Program<'info, Token>,
+    amount: u64,
+) -> Result<()> {
+    let nonce = option.client_nonce.to_le_bytes();
+    let seeds: &[&[u8]] = &[b"option", OPTION_SEED_VERSION, option.option_id.as_bytes(), &nonce, &[option.bump]];
+    token::transfer(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            token::Transfer {
+                from: escrow.to_account_info(),
+                to: to.to_account_info(),
+                authority: option.to_account_info(),
+            },
+            &[seeds],
+        ),
+        amount,
+    )
+}
+
+/// SPL-premium options must pass the escrow, a destination token account
+/// and the token program; SOL-premium options leave them out.
+fn unpack_spl_accounts<'a, 'info>(
+    escrow: &'a Option<Account<'info, TokenAccount>>,
+    destination: &'a Option<Account<'info, TokenAccount>>,
+    token_program: &'a Option<Program<'info, Token>>,
+) -> Result<(
+    &'a Account<'info, TokenAccount>,
+    &'a Account<'info, TokenAccount>,
+    &'a Program<'info, Token>,
+)> {
+    match (escrow, destination, token_program) {
+        (Some(escrow), Some(destination), Some(token_program)) => {
+            Ok((escrow, destination, token_program))
+        }
+        _ => err!(QuorumError::MissingTokenAccounts),
+    }
+}
+
+// ============================================================================
+// ACCOUNT STRUCTS
+// ============================================================================
+
+#[account]
+#[derive(Default)]
+pub struct OptionContract {
+    pub option_id: String,          // unique ID (max 32 chars)
+    pub event_name: String,         // "Florist" (max 64 chars)
+    pub event_date: String,         // "2026-03-01" (max 16 chars)
+    pub ticket_type: String,        // "GA Early Bird" (max 32 chars)
+    pub quantity: u8,               // number of tickets (remaining unexercised)
+    pub premium_lamports: u64,      // premium held, net of protocol fee (lamports, or token base units if premium_mint is set)
+    pub holder: Pubkey,             // fan's wallet
+    pub expiry: i64,                // unix timestamp
+    pub status: u8,                 // 0=Active, 1=Exercised, 2=Expired, 3=Cancelled, 4=Listed
+    pub created_at: i64,            // unix timestamp
+    pub venue_royalty_bps: u16,     // basis points (1000 = 10%)
+    pub bump: u8,                   // PDA bump seed
+    pub premium_mint: Option<Pubkey>, // None = native SOL, Some = SPL token mint
+    pub strike_lamports: u64,       // face value per ticket, paid on exercise
+    pub writer: Pubkey,             // venue that wrote the option, collects proceeds
+    pub creator: Pubkey,            // paid the PDA rent, gets it back on close
+    pub cancel_refund_bps: u16,     // share of premium refunded on cancel (10000 = 100%)
+    pub venue: Pubkey,              // receives venue_royalty_bps of the strike
+    pub exercise_window_start: i64, // 0 = American, else earliest exercise timestamp
+    pub pool: Option<Pubkey>,       // shared exercise cap, if any
+    pub premium_start: u64,         // Dutch auction start price (0 = fixed premium)
+    pub premium_floor: u64,         // Dutch auction floor price
+    pub decay_end: i64,             // when the Dutch premium bottoms out
+    pub exercised_qty: u8,          // tickets exercised and not yet relisted
+    pub parent_option: Option<Pubkey>, // exercised option this was relisted from, or split from
+    pub delegate: Option<Pubkey>,   // may exercise on the holder's behalf
+    pub nft_mint: Option<Pubkey>,   // if set, the owner of this supply-1 token is the holder
+    pub co_holders: Vec<CoHolder>,  // if set, these wallets exercise their shares instead
+    pub disputed: bool,             // holder raised a dispute; premium frozen until resolved
+    pub auto_exercise: bool,        // settle_at_expiry exercises instead of expiring
+    pub strike_escrowed: u64,       // prefunded strike for auto-exercise, held in the PDA
+    pub pending_strike: u64,        // strike held in the PDA until the venue confirms issuance
+    pub pending_royalty: u64,       // venue's cut of pending_strike
+    pub pending_qty: u8,            // tickets awaiting issuance
+    pub pending_payer: Pubkey,      // who a failed issuance refunds
+    pub pending_from_escrow: bool,  // pending_strike came out of strike_escrowed
+    pub issuance_deadline: i64,     // after this, refund_failed_issuance opens
+    pub schema_version: u8,         // OPTION_SCHEMA_VERSION this account was written with
+    pub refund_decay_start: i64,    // cancel refunds decay from here to expiry
+    pub warned: bool,               // ExpiryWarning already emitted for the current expiry
+    pub strike_mint: Option<Pubkey>, // if set, strike is paid in this token into the strike escrow
+    pub frozen: bool,               // authority/writer froze it: no exercise, transfer, cancel or expiry
+    pub client_nonce: u64,          // creator's retry key from CreateOptionParams; in the PDA seeds
+    pub transferable: bool,         // false: no transfer, bid fill, NFT or relist — only the holder exercises
+    pub pending_fee: u64,           // treasury's exercise fee out of pending_strike
+    pub metadata_uri: String,       // off-chain display JSON, empty = none
+    pub identity_hash: Option<[u8; 32]>, // only a wallet bound to this identity exercises
+    pub pending_co_holder: Option<Pubkey>, // co-holder whose shares a failed issuance restores
+}
+
+impl OptionContract {
+    /// Decoded status — fails on a byte no OptionStatus maps to.
+    pub fn status_enum(&self) -> Result<OptionStatus> {
+        OptionStatus::try_from(self.status)
+    }
+
+    /// Move to `to`, rejecting anything can_transition doesn't allow.
+    pub fn set_status(&mut self, to: OptionStatus) -> Result<()> {
+        require!(can_transition(self.status_enum()?, to), QuorumError::InvalidTransition);
+        self.status = to as u8;
+        Ok(())
+    }
+
+    // 8 discriminator + actual data
+    // Strings: 4 bytes length prefix + content
+    pub const MAX_SIZE: usize = 8
+        + (4 + MAX_OPTION_ID_LEN as usize)   // option_id
+        + (4 + MAX_EVENT_NAME_LEN as usize)  // event_name
+        + (4 + MAX_EVENT_DATE_LEN as usize)  // event_date
+        + (4 + MAX_TICKET_TYPE_LEN as usize) // ticket_type
+        + 1          // quantity
+        + 8          // premium_lamports
+        + 32         // holder pubkey
+        + 8          // expiry
+        + 1          // status
+        + 8          // created_at
+        + 2          // venue_royalty_bps
+        + 1          // bump
+        + (1 + 32)   // premium_mint
+        + 8          // strike_lamports
+        + 32         // writer
+        + 32         // creator
+        + 2          // cancel_refund_bps
+        + 32         // venue
+        + 8          // exercise_window_start
+        + (1 + 32)   // pool
+        + 8          // premium_start
+        + 8          // premium_floor
+        + 8          // decay_end
+        + 1          // exercised_qty
+        + (1 + 32)   // parent_option
+        + (1 + 32)   // delegate
+        + (1 + 32)   // nft_mint
+        + 4 + MAX_CO_HOLDERS * CoHolder::SIZE // co_holders
+        + 1          // disputed
+        + 1          // auto_exercise
+        + 8          // strike_escrowed
+        + 8          // pending_strike
+        + 8          // pending_royalty
+        + 1          // pending_qty
+        + 32         // pending_payer
+        + 1          // pending_from_escrow
+        + 8          // issuance_deadline
+        + 1          // schema_version
+        + 8          // refund_decay_start
+        + 1          // warned
+        + (1 + 32)   // strike_mint
+        + 1          // frozen
+        + 8          // client_nonce
+        + 1          // transferable
+        + 8          // pending_fee
+        + (4 + MAX_METADATA_URI_LEN as usize) // metadata_uri
+        + (1 + 32)   // identity_hash
+        + (1 + 32);  // pending_co_holder
+}
+
+/// OptionContract as the first release wrote it, at [b"option", option_id]
+/// under the same account discriminator. Only migrate_option reads it.
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct LegacyOptionContractV0 {
+    pub option_id: String,
+    pub event_name: String,
+    pub event_date: String,
+    pub ticket_type: String,
+    pub quantity: u8,
+    pub premium_lamports: u64,
+    pub holder: Pubkey,
+    pub expiry: i64,
+    pub status: u8,                 // 0=Active, 1=Exercised, 2=Expired — same values as today
+    pub created_at: i64,
+    pub venue_royalty_bps: u16,
+    pub bump: u8,
+}
+
+impl LegacyOptionContractV0 {
+    /// The current-layout option for a legacy one. Version 0 had no writer,
+    /// venue or strike: the holder stood in for all three parties and
+    /// exercise paid nothing, so that's what carries over.
+    pub fn into_current(self, bump: u8) -> OptionContract {
+        OptionContract {
+            option_id: self.option_id,
+            event_name: self.event_name,
+            event_date: self.event_date,
+            ticket_type: self.ticket_type,
+            quantity: self.quantity,
+            premium_lamports: self.premium_lamports,
+            holder: self.holder,
+            expiry: self.expiry,
+            status: self.status,
+            created_at: self.created_at,
+            venue_royalty_bps: self.venue_royalty_bps,
+            bump,
+            strike_lamports: 0,
+            writer: self.holder,
+            creator: self.holder,
+            venue: self.holder,
+            refund_decay_start: self.created_at,
+            schema_version: OPTION_SCHEMA_VERSION,
+            client_nonce: 0,
+            // Nothing locked resale before the flag existed
+            transferable: true,
+            ..Default::default()
+        }
+    }
+}
+
+/// Durable record of what was exercised on an option, for the venue to
+/// issue tickets against. Partial exercises accumulate into one receipt.
+#[account]
+#[derive(Default)]
+pub struct ExerciseReceipt {
+    pub option: Pubkey,             // option PDA this receipt belongs to
+    pub option_id: String,          // copied so the receipt reads on its own (max 32 chars)
+    pub option_created_at: i64,     // pins the receipt to one incarnation of option_id
+    pub holder: Pubkey,             // holder at the latest exercise
+    pub quantity: u8,               // tickets exercised in total
+    pub strike_paid: u64,           // lamports paid in total
+    pub exercised_at: i64,          // latest exercise timestamp
+    pub bump: u8,                   // PDA bump seed
+    pub beneficiary: Pubkey,        // tickets go here — the holder unless exercise_to named someone
+}
+
+impl ExerciseReceipt {
+    pub const MAX_SIZE: usize = 8
+        + 32         // option
+        + (4 + MAX_OPTION_ID_LEN as usize) // option_id
+        + 8          // option_created_at
+        + 32         // holder
+        + 1          // quantity
+        + 8          // strike_paid
+        + 8          // exercised_at
+        + 1          // bump
+        + 32;        // beneficiary
+}
+
+/// Per-wallet live option count, for fair allocation on hot drops.
+#[account]
+pub struct HolderStats {
+    pub holder: Pubkey,
+    pub active_count: u16,          // options currently Active
+    pub bump: u8,                   // PDA bump seed
+    pub last_action_ts: i64,        // last cancel, for the create cooldown
+}
+
+impl HolderStats {
+    pub const MAX_SIZE: usize = 8
+        + 32         // holder
+        + 2          // active_count
+        + 1          // bump
+        + 8;         // last_action_ts
+}
+
+/// Protocol-funded pot for expiry keeper rewards, so cranking doesn't eat
+/// into writer premiums. `balance` tracks what's been funded and not yet
+/// paid out; the account's rent reserve is never counted.
+#[account]
+pub struct KeeperTreasury {
+    pub balance: u64,               // lamports available for rewards
+    pub total_paid: u64,            // lifetime rewards paid to keepers
+    pub bump: u8,                   // PDA bump seed
+}
+
+impl KeeperTreasury {
+    pub const MAX_SIZE: usize = 8
+        + 8          // balance
+        + 8          // total_paid
+        + 1;         // bump
+}
+
+/// The right to take a standard option on `underlying` terms until
+/// `expiry`. One PDA per underlying option, at [b"compound", option_id,
+/// client_nonce]; it closes when exercised or expired. The child option's
+/// PDA sits Reserved alongside it for as long as it's open.
+#[account]
+pub struct CompoundOption {
+    pub holder: Pubkey,             // who may exercise, and gets the rent back
+    pub writer: Pubkey,             // receives the compound premium
+    pub premium_lamports: u64,      // compound premium held, net of the protocol fee
+    pub expiry: i64,                // last moment the underlying can be taken
+    pub created_at: i64,
+    pub bump: u8,                   // PDA bump seed
+    pub underlying: CreateOptionParams, // the child option's terms, writer pinned
+}
+
+impl CompoundOption {
+    pub const MAX_SIZE: usize = 8
+        + 32         // holder
+        + 32         // writer
+        + 8          // premium_lamports
+        + 8          // expiry
+        + 8          // created_at
+        + 1          // bump
+        + CreateOptionParams::MAX_SIZE; // underlying
+}
+
+/// Program-wide lifecycle counters for dashboards, one PDA at
+/// [b"global_stats"]. active_count follows HolderStats: an option leaves it
+/// when fully exercised and returns if the issuance is refunded.
+#[account]
+#[derive(Default)]
+pub struct GlobalStats {
+    pub total_options_created: u64, // options that went live (Dutch listings on claim)
+    pub total_premium_volume: u64,  // lamports; token premiums aren't counted
+    pub total_exercised: u64,       // options whose issuance was confirmed
+    pub total_expired: u64,
+    pub total_cancelled: u64,
+    pub active_count: u64,          // live options across every holder
+    pub bump: u8,                   // PDA bump seed
+}
+
+impl GlobalStats {
+    pub const MAX_SIZE: usize = 8
+        + 8          // total_options_created
+        + 8          // total_premium_volume
+        + 8          // total_exercised
+        + 8          // total_expired
+        + 8          // total_cancelled
+        + 8          // active_count
+        + 1;         // bump
+
+    pub fn record_created(&mut self, count: u64, premium: u64) {
+        self.total_options_created = self.total_options_created.saturating_add(count);
+        self.total_premium_volume = self.total_premium_volume.saturating_add(premium);
+        self.active_count = self.active_count.saturating_add(count);
+    }
+
+    pub fn add_active(&mut self, count: u64) {
+        self.active_count = self.active_count.saturating_add(count);
+    }
+
+    pub fn remove_active(&mut self, count: u64) {
+        self.active_count = self.active_count.saturating_sub(count);
+    }
+
+    pub fn record_expired(&mut self) {
+        self.total_expired = self.total_expired.saturating_add(1);
+        self.remove_active(1);
+    }
+
+    pub fn record_cancelled(&mut self, was_active: bool) {
+        self.total_cancelled = self.total_cancelled.saturating_add(1);
+        if was_active {
+            self.remove_active(1);
+        }
+    }
+}
+
+#[account]
+pub struct OptionPool {
+    pub writer: Pubkey,             // venue that owns the inventory
+    pub pool_id: u64,               // writer-chosen ID
+    pub total_cap: u32,             // max tickets exercisable across the pool
+    pub exercised_count: u32,       // tickets exercised so far
+    pub bump: u8,                   // PDA bump seed
+}
+
+impl OptionPool {
+    pub const MAX_SIZE: usize = 8
+        + 32         // writer
+        + 8          // pool_id
+        + 4          // total_cap
+        + 4          // exercised_count
+        + 1;         // bump
+}
+
+/// Approved venue wallet, for permissioned deployments.
+#[account]
+pub struct WriterWhitelist {
+    pub writer: Pubkey,             // approved venue wallet
+    pub bump: u8,                   // PDA bump seed
+}
+
+impl WriterWhitelist {
+    pub const MAX_SIZE: usize = 8
+        + 32         // writer
+        + 1;         // bump
+}
+
+/// A venue's attestation that a wallet passed its identity check.
+/// Seeds: [b"identity", venue, wallet].
+#[account]
+pub struct HolderIdentity {
+    pub venue: Pubkey,              // venue that verified the wallet
+    pub wallet: Pubkey,
+    pub identity_hash: [u8; 32],    // hash of the off-chain verified identity
+    pub bump: u8,
+}
+
+impl HolderIdentity {
+    pub const MAX_SIZE: usize = 8
+        + 32         // venue
+        + 32         // wallet
+        + 32         // identity_hash
+        + 1;         // bump
+}
+
+/// Key a venue's off-chain pricer signs quotes with. Seeds: [b"quoter", venue].
+#[account]
+pub struct VenueQuoter {
+    pub venue: Pubkey,
+    pub signer: Pubkey,             // ed25519 key quotes must be signed by
+    pub bump: u8,
+}
+
+impl VenueQuoter {
+    pub const MAX_SIZE: usize = 8
+        + 32         // venue
+        + 32         // signer
+        + 1;         // bump
+}
+
+/// Marks a signed message's nonce as spent. Seeds: [b"nonce", venue, nonce
+/// as le bytes] for quotes, [b"relay_nonce", holder, nonce as le bytes] for
+/// relayed exercises.
+#[account]
+pub struct QuoteNonce {
+    pub venue: Pubkey,              // the fan's wallet on a relay nonce
+    pub nonce: u64,
+    pub used_at: i64,
+    pub bump: u8,
+}
+
+impl QuoteNonce {
+    pub const MAX_SIZE: usize = 8
+        + 32         // venue
+        + 8          // nonce
+        + 8          // used_at
+        + 1;         // bump
+}
+
+/// Running per-event demand totals, readable without indexing every option.
+/// Counts are options; total_premium_collected is SOL premiums only (net of
+/// protocol fee, including extend_expiry top-ups) since token units don't mix.
+/// demand_score folds in each new SOL option's premium over its face value:
+/// the more fans pay just for the right to buy, the hotter the event.
+/// price_history is a ring buffer of the last PRICE_HISTORY_LEN SOL premiums
+/// for sparklines: read the price_history_count entries ending just before
+/// price_history_head, oldest first. Once full, each new option overwrites
+/// the oldest sample.
+#[account]
+#[derive(Default)]
+pub struct EventStats {
+    pub event_hash: [u8; 32],       // event_seed(event_name)
+    pub total_options_created: u64, // options that went live (Dutch listings on claim)
+    pub total_premium_collected: u64, // lamports
+    pub total_exercised: u64,       // options fully exercised
+    pub total_expired: u64,         // options expired unexercised
+    pub bump: u8,                   // PDA bump seed
+    pub demand_score: u64,          // moving average of premium / face value, DEMAND_SCORE_SCALE = 1.0
+    pub demand_samples: u64,        // options folded into demand_score
+    pub price_history: [PriceObservation; PRICE_HISTORY_LEN], // ring buffer, see above
+    pub price_history_head: u8,     // next slot written; the oldest once full
+    pub price_history_count: u8,    // filled slots, capped at PRICE_HISTORY_LEN
+}
+
+impl EventStats {
+    pub const MAX_SIZE: usize = 8
+        + 32         // event_hash
+        + 8          // total_options_created
+        + 8          // total_premium_collected
+        + 8          // total_exercised
+        + 8          // total_expired
+        + 1          // bump
+        + 8          // demand_score
+        + 8          // demand_samples
+        + PRICE_HISTORY_LEN * PriceObservation::SIZE // price_history
+        + 1          // price_history_head
+        + 1;         // price_history_count
+}
+
+/// One EventStats::price_history sample: a new option's net SOL premium.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct PriceObservation {
+    pub timestamp: i64,
+    pub premium: u64,               // lamports, net of protocol fee
+}
+
+impl PriceObservation {
+    pub const SIZE: usize = 8 + 8;
+}
+
+/// A writer's expired premiums, vesting before it can claim them.
+/// Seeds: [b"vault", writer].
+#[account]
+pub struct WriterVault {
+    pub writer: Pubkey,
+    pub bump: u8,                   // PDA bump seed
+    pub locked: u64,                // deposited, not yet vested
+    pub unlocked: u64,              // vested, not yet claimed
+    pub last_update: i64,           // locked vests linearly from here...
+    pub vesting_end: i64,           // ...to here
+    pub total_claimed: u64,         // lifetime claims, for accounting
+}
+
+impl WriterVault {
+    pub const MAX_SIZE: usize = 8
+        + 32         // writer
+        + 1          // bump
+        + 8          // locked
+        + 8          // unlocked
+        + 8          // last_update
+        + 8          // vesting_end
+        + 8;         // total_claimed
+}
+
+/// Per-event list of option PDAs, so clients can enumerate an event's
+/// options without a getProgramAccounts scan. Seeds: [b"index", event_seed(event_name)].
+/// Grows on create, shrinks on close_option.
+#[account]
+pub struct EventIndex {
+    pub event_hash: [u8; 32],       // event_seed(event_name)
+    pub bump: u8,
+    pub options: Vec<Pubkey>,       // live and settled-but-unclosed options
+}
+
+impl EventIndex {
+    /// Account size holding `len` option keys.
+    pub fn space(len: usize) -> usize {
+        8 + 32 + 1 + 4 + len * 32
+    }
+}
+
+/// Per-(writer, event) flags the writer controls, e.g. venue cancellation.
+#[account]
+pub struct EventRegistry {
+    pub writer: Pubkey,             // venue that owns the event
+    pub event_hash: [u8; 32],       // event_seed(event_name)
+    pub cancelled: bool,            // show is off — holders can claim full refunds
+    pub bump: u8,                   // PDA bump seed
+    pub clearing_premium: u64,      // finalized uniform premium, 0 = not set
+    pub ticket_types: Vec<String>,  // canonical labels, empty = any (max MAX_TICKET_TYPES × 32 chars)
+    pub royalty_tiers: Vec<RoyaltyTier>, // quantity-based royalty overrides (max MAX_ROYALTY_TIERS)
+    pub presale_root: Option<[u8; 32]>, // merkle root of allowlisted claimants, None = open
+    pub event_expiry: i64,          // doors-close time every new option uses, 0 = per-option
+    pub max_options: u32,           // cap on options written for the event, 0 = uncapped
+    pub options_issued: u32,        // options counted against max_options, less cancellations
+    pub lot_size: u8,               // tickets sell in multiples of this, 0 or 1 = any
+    pub settlement_account: Pubkey, // receives strike payouts, default = the option's writer
+}
+
+impl EventRegistry {
+    pub const MAX_SIZE: usize = 8
+        + 32         // writer
+        + 32         // event_hash
+        + 1          // cancelled
+        + 1          // bump
+        + 8          // clearing_premium
+        + 4 + MAX_TICKET_TYPES * (4 + MAX_TICKET_TYPE_LEN as usize) // ticket_types
+        + 4 + MAX_ROYALTY_TIERS * RoyaltyTier::SIZE // royalty_tiers
+        + (1 + 32)   // presale_root
+        + 8          // event_expiry
+        + 4          // max_options
+        + 4          // options_issued
+        + 1          // lot_size
+        + 32;        // settlement_account
+}
+
+/// Options covering at least min_quantity tickets pay bps royalty.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct RoyaltyTier {
+    pub min_quantity: u8,
+    pub bps: u16,
+}
+
+impl RoyaltyTier {
+    pub const SIZE: usize = 1 + 2;
+}
+
+/// Per-event bid book. Bid lamports are escrowed in this account.
+#[account]
+pub struct MarketBook {
+    pub event_hash: [u8; 32],       // event_seed(event_name)
+    pub next_bid_id: u64,           // monotonically increasing bid IDs
+    pub bump: u8,                   // PDA bump seed
+    pub bids: Vec<Bid>,             // resting bids (max MAX_BOOK_BIDS)
+}
+
+impl MarketBook {
+    pub const MAX_SIZE: usize = 8
+        + 32         // event_hash
+        + 8          // next_bid_id
+        + 1          // bump
+        + 4 + MAX_BOOK_BIDS * Bid::SIZE; // bids
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct Bid {
+    pub bid_id: u64,
+    pub bidder: Pubkey,
+    pub price_lamports: u64,        // paid to the seller on fill
+    pub min_quantity: u8,           // smallest option (in tickets) the bidder will take
+}
+
+impl Bid {
+    pub const SIZE: usize = 8 + 32 + 8 + 1;
+}
+
+/// Creation terms shared by create_option, create_option_spl and
+/// create_options_batch, and held by a CompoundOption until exercised.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CreateOptionParams {
+    pub option_id: String,
+    pub event_name: String,
+    pub event_date: String,
+    pub ticket_type: String,
+    pub quantity: u8,
+    pub premium_lamports: u64,      // token base units on the SPL path
+    pub strike_lamports: u64,       // face value per ticket
+    pub expiry: i64,
+    pub venue_royalty_bps: u16,
+    pub cancel_refund_bps: u16,
+    pub writer: Option<Pubkey>,     // defaults to the holder
+    pub venue: Option<Pubkey>,      // defaults to the writer
+    pub exercise_window_start: i64, // 0 = American style
+    pub co_holders: Vec<CoHolder>,  // group buy allocations, empty = sole holder
+    pub auto_exercise: bool,        // prefund the strike so a keeper exercises at expiry
+    pub client_nonce: u64,          // retry key, part of the option PDA seeds
+    pub transferable: Option<bool>, // defaults to true; false locks out resale
+    pub metadata_uri: Option<String>, // off-chain display JSON, defaults to none
+    pub identity_hash: Option<[u8; 32]>, // KYC binding, None = anyone may exercise
+}
+
+impl CreateOptionParams {
+    // Borsh size at the string and co-holder maxima, for accounts that store a set of terms
+    pub const MAX_SIZE: usize = (4 + MAX_OPTION_ID_LEN as usize) // option_id
+        + (4 + MAX_EVENT_NAME_LEN as usize)  // event_name
+        + (4 + MAX_EVENT_DATE_LEN as usize)  // event_date
+        + (4 + MAX_TICKET_TYPE_LEN as usize) // ticket_type
+        + 1          // quantity
+        + 8          // premium_lamports
+        + 8          // strike_lamports
+        + 8          // expiry
+        + 2          // venue_royalty_bps
+        + 2          // cancel_refund_bps
+        + (1 + 32)   // writer
+        + (1 + 32)   // venue
+        + 8          // exercise_window_start
+        + 4 + MAX_CO_HOLDERS * CoHolder::SIZE // co_holders
+        + 1          // auto_exercise
+        + 8          // client_nonce
+        + (1 + 1)    // transferable
+        + (1 + 4 + MAX_METADATA_URI_LEN as usize) // metadata_uri
+        + (1 + 32);  // identity_hash
+}
+
+/// One child of split_option: its ticket count and the client_nonce that
+/// seeds its PDA alongside the parent's option_id.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct SplitLeg {
+    pub quantity: u8,
+    pub client_nonce: u64,
+}
+
+/// One wallet's slice of a co-held option: how many of its tickets it may
+/// still exercise.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CoHolder {
+    pub holder: Pubkey,
+    pub shares: u8,
+}
+
+impl CoHolder {
+    pub const SIZE: usize = 32 + 1;
+}
+
+/// Venue-signed price for create_option_with_quote. The signed message is
+/// this struct's borsh encoding.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct Quote {
+    pub event_name: String,
+    pub ticket_type: String,
+    pub premium_lamports: u64,
+    pub expiry: i64,
+    pub nonce: u64,                 // single-use per venue
+    pub valid_until: i64,           // quote is stale after this
+}
+
+/// Fan-signed authorization for exercise_option_relayed. The signed message
+/// is this struct's borsh encoding.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct RelayedExercise {
+    pub option_id: String,
+    pub client_nonce: u64,          // with option_id, pins the exact option PDA
+    pub holder: Pubkey,             // ed25519 key that signed; must be able to exercise
+    pub nonce: u64,                 // single-use per holder
+    pub exercise_qty: u8,
+    pub max_total_cost: u64,        // strike cap the relayer can't exceed
+    pub valid_until: i64,           // authorization is stale after this
+}
+
+/// Partial config update for update_config — only Some fields are applied.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct ConfigUpdate {
+    pub max_per_holder: Option<u16>,
+    pub protocol_fee_bps: Option<u16>,
+    pub treasury: Option<Pubkey>,
+    pub expire_reward_lamports: Option<u64>,
+    pub min_premium_bps: Option<u16>,
+    pub max_quantity: Option<u8>,
+    pub grace_seconds: Option<u32>,
+    pub whitelist_enabled: Option<bool>,
+    pub min_expiry_seconds: Option<u32>,
+    pub action_cooldown_seconds: Option<u32>,
+    pub dispute_window_seconds: Option<u32>,
+    pub max_option_id_len: Option<u8>,
+    pub max_event_name_len: Option<u8>,
+    pub max_event_date_len: Option<u8>,
+    pub max_ticket_type_len: Option<u8>,
+    pub issuance_timeout_seconds: Option<u32>,
+    pub max_royalty_bps: Option<u16>,
+    pub writer_vesting_seconds: Option<u32>,
+    pub expiry_warning_seconds: Option<u32>,
+    pub min_premium_lamports: Option<u64>,
+    pub max_premium_lamports: Option<u64>,
+    pub exercise_fee_bps: Option<u16>,
+    pub event_expiry_buffer_seconds: Option<u32>,
+    pub late_exercise_penalty_bps: Option<u16>,
+}
+
+/// Return data of exercise_option, exercise_to and exercise_partial.
+/// Borsh layout, 11 bytes: exercised_qty u8, strike_paid u64 LE (lamports,
+/// or strike-token base units), status u8 (an OptionStatus — PendingIssuance
+/// once no tickets remain, else Active), remaining_qty u8.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ExerciseResult {
+    pub exercised_qty: u8,
+    pub strike_paid: u64,
+    pub status: u8,
+    pub remaining_qty: u8,
+}
+
+/// Computed view returned by get_option_state.
+/// is_expired is purely time-based: an Active option past expiry is
+/// "expired but not settled" until someone cranks expire_option.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct OptionState {
+    pub is_active: bool,
+    pub is_exercisable_now: bool,
+    pub seconds_to_expiry: i64,     // 0 once expiry has passed
+    pub is_expired: bool,
+}
+
+#[account]
+pub struct ProgramConfig {
+    pub authority: Pubkey,          // can pause/unpause the program
+    pub paused: bool,               // blocks new option creation when true
+    pub bump: u8,                   // PDA bump seed
+    pub max_per_holder: u16,        // max live options per wallet, 0 = unlimited
+    pub protocol_fee_bps: u16,      // cut of every premium sent to the treasury
+    pub treasury: Pubkey,           // receives protocol fees
+    pub expire_reward_lamports: u64, // paid from the KeeperTreasury to whoever cranks expire_option
+    pub min_premium_bps: u16,       // SOL premium floor as a share of total face value
+    pub max_quantity: u8,           // max tickets per option
+    pub grace_seconds: u32,         // exercise stays open this long past expiry
+    pub whitelist_enabled: bool,    // only WriterWhitelist entries may write options
+    pub min_expiry_seconds: u32,    // shortest allowed time from creation to expiry
+    pub action_cooldown_seconds: u32, // wait after a cancel before creating again
+    pub pending_authority: Option<Pubkey>, // nominated by propose_authority, not yet accepted
+    pub dispute_window_seconds: u32, // holder may dispute this long after the exercise deadline
+    pub max_option_id_len: u8,      // string caps, each ≤ the matching MAX_*_LEN
+    pub max_event_name_len: u8,
+    pub max_event_date_len: u8,
+    pub max_ticket_type_len: u8,
+    pub issuance_timeout_seconds: u32, // venue has this long to confirm issuance before a refund opens
+    pub max_royalty_bps: u16,       // ceiling on venue royalties, option-level and tiered
+    pub writer_vesting_seconds: u32, // expired premiums vest in the WriterVault over this long (0 = at once)
+    pub expiry_warning_seconds: u32, // ping_expiry_warning opens this long before expiry
+    pub min_premium_lamports: u64,  // absolute SOL premium floor, 0 = no bound
+    pub max_premium_lamports: u64,  // absolute SOL premium ceiling, 0 = no bound
+    pub exercise_fee_bps: u16,      // treasury's cut of every exercised strike, on top of the royalty
+    pub event_expiry_buffer_seconds: u32, // expiry may run this far past 00:00 UTC of event_date
+    pub late_exercise_penalty_bps: u16, // added to the strike for exercising in the grace window, paid out with the strike remainder to the settlement account
+}
+
+impl ProgramConfig {
+    pub const MAX_SIZE: usize = 8
+        + 32         // authority
+        + 1          // paused
+        + 1          // bump
+        + 2          // max_per_holder
+        + 2          // protocol_fee_bps
+        + 32         // treasury
+        + 8          // expire_reward_lamports
+        + 2          // min_premium_bps
+        + 1          // max_quantity
+        + 4          // grace_seconds
+        + 1          // whitelist_enabled
+        + 4          // min_expiry_seconds
+        + 4          // action_cooldown_seconds
+        + (1 + 32)   // pending_authority
+        + 4          // dispute_window_seconds
+        + 1          // max_option_id_len
+        + 1          // max_event_name_len
+        + 1          // max_event_date_len
+        + 1          // max_ticket_type_len
+        + 4          // issuance_timeout_seconds
+        + 2          // max_royalty_bps
+        + 4          // writer_vesting_seconds
+        + 4          // expiry_warning_seconds
+        + 8          // min_premium_lamports
+        + 8          // max_premium_lamports
+        + 2          // exercise_fee_bps
+        + 4          // event_expiry_buffer_seconds
+        + 2;         // late_exercise_penalty_bps
+}
+
+// Option lifecycle states
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum OptionStatus {
+    Active = 0,
+    Exercised = 1,
+    Expired = 2,
+    Cancelled = 3,
+    Listed = 4,      // Dutch auction written, waiting for a buyer
+    PendingIssuance = 5, // fully exercised, strike held until the venue confirms issuance
+    Reserved = 6,    // address held for a compound option until it's exercised or lapses
+}
+
+impl TryFrom<u8> for OptionStatus {
+    type Error = Error;
+
+    fn try_from(value: u8) -> Result<Self> {
+        match value {
+            0 => Ok(OptionStatus::Active),
+            1 => Ok(OptionStatus::Exercised),
+            2 => Ok(OptionStatus::Expired),
+            3 => Ok(OptionStatus::Cancelled),
+            4 => Ok(OptionStatus::Listed),
+            5 => Ok(OptionStatus::PendingIssuance),
+            6 => Ok(OptionStatus::Reserved),
+            _ => err!(QuorumError::InvalidStatus),
+        }
+    }
+}
+
+// What a LamportFlow event paid for
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum FlowKind {
+    Premium = 0,      // holder → option PDA
+    Strike = 1,       // holder → writer, net of royalty
+    Royalty = 2,      // holder → venue
+    Refund = 3,       // PDA → holder or bidder
+    Fee = 4,          // holder → treasury
+    KeeperReward = 5, // keeper treasury → expiring keeper
+    Withdrawal = 6,   // option PDA → writer
+    BidEscrow = 7,    // bidder → market book
+    Sale = 8,         // market book → selling holder
+    StrikeEscrow = 9, // holder → option PDA, prefunding an auto-exercise strike
+}
+
+// ============================================================================
+// CONTEXT STRUCTS
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct InitializeConfig<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = ProgramConfig::MAX_SIZE,
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetPaused<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = authority @ QuorumError::UnauthorizedAuthority
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = authority @ QuorumError::UnauthorizedAuthority
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = authority @ QuorumError::UnauthorizedAuthority
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptAuthority<'info> {
+    #[account(mut, seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, ProgramConfig>,
+
+    pub new_authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(writer: Pubkey)]
+pub struct AddWriter<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = authority @ QuorumError::UnauthorizedAuthority
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = WriterWhitelist::MAX_SIZE,
+        seeds = [b"writer", writer.as_ref()],
+        bump
+    )]
+    pub writer_entry: Account<'info, WriterWhitelist>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetQuoteSigner<'info> {
+    #[account(
+        init_if_needed,
+        payer = venue,
+        space = VenueQuoter::MAX_SIZE,
+        seeds = [b"quoter", venue.key().as_ref()],
+        bump
+    )]
+    pub quoter: Account<'info, VenueQuoter>,
+
+    #[account(mut)]
+    pub venue: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(wallet: Pubkey)]
+pub struct BindIdentity<'info> {
+    #[account(
+        init_if_needed,
+        payer = venue,
+        space = HolderIdentity::MAX_SIZE,
+        seeds = [b"identity", venue.key().as_ref(), wallet.as_ref()],
+        bump
+    )]
+    pub identity: Account<'info, HolderIdentity>,
+
+    #[account(mut)]
+    pub venue: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveWriter<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = authority @ QuorumError::UnauthorizedAuthority
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"writer", writer_entry.writer.as_ref()],
+        bump = writer_entry.bump,
+        close = authority
+    )]
+    pub writer_entry: Account<'info, WriterWhitelist>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(pool_id: u64)]
+pub struct CreatePool<'info> {
+    #[account(
+        init,
+        payer = writer,
+        space = OptionPool::MAX_SIZE,
+        seeds = [b"pool", writer.key().as_ref(), pool_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub pool: Account<'info, OptionPool>,
+
+    #[account(mut)]
+    pub writer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(params: CreateOptionParams)]
+pub struct CreateOption<'info> {
+    #[account(
+        init_if_needed,
+        payer = holder,
+        space = OptionContract::MAX_SIZE,
+        seeds = [b"option", OPTION_SEED_VERSION, params.option_id.as_bytes(), &params.client_nonce.to_le_bytes()],
+        bump
+    )]
+    pub option_contract: Account<'info, OptionContract>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, ProgramConfig>,
+
+    // Required when config.whitelist_enabled: the writer's whitelist entry
+    pub writer_entry: Option<Account<'info, WriterWhitelist>>,
+
+    /// CHECK: writer's EventRegistry PDA for this event — may not exist yet
+    #[account(mut)]
+    pub event_registry: UncheckedAccount<'info>,
+
+    /// CHECK: receives the protocol fee only; must match config.treasury
+    #[account(mut, address = config.treasury @ QuorumError::InvalidTreasury)]
+    pub treasury: UncheckedAccount<'info>,
+
+    pub pool: Option<Account<'info, OptionPool>>,
+
+    #[account(
+        init_if_needed,
+        payer = holder,
+        space = HolderStats::MAX_SIZE,
+        seeds = [b"holder", holder.key().as_ref()],
+        bump
+    )]
+    pub holder_stats: Account<'info, HolderStats>,
+
+    #[account(mut)]
+    pub holder: Signer<'info>,
+
+    /// CHECK: EventStats PDA for the option's event; created on first use
+    #[account(mut)]
+    pub event_stats: UncheckedAccount<'info>,
+
+    /// CHECK: EventIndex PDA for the option's event; created on first use
+    #[account(mut)]
+    pub event_index: UncheckedAccount<'info>,
+
+    #[account(mut, seeds = [b"global_stats"], bump = global_stats.bump)]
+    pub global_stats: Account<'info, GlobalStats>,
+
+    pub system_program: Program<'info, System>,
+
+    pub clock: Option<Sysvar<'info, Clock>>,
+}
+
+#[derive(Accounts)]
+#[instruction(params: CreateOptionParams)]
+pub struct CreateOptionSpl<'info> {
+    #[account(
+        init_if_needed,
+        payer = holder,
+        space = OptionContract::MAX_SIZE,
+        seeds = [b"option", OPTION_SEED_VERSION, params.option_id.as_bytes(), &params.client_nonce.to_le_bytes()],
+        bump
+    )]
+    pub option_contract: Account<'info, OptionContract>,
+
+    // Escrow token account owned by the option PDA
+    #[account(
+        init_if_needed,
+        payer = holder,
+        seeds = [b"escrow", option_contract.key().as_ref()],
+        bump,
+        token::mint = premium_mint,
+        token::authority = option_contract,
+    )]
+    pub premium_escrow: Account<'info, TokenAccount>,
+
+    pub premium_mint: Account<'info, Mint>,
+
+    // Set both to take the strike in a token: its own escrow, owned by the option PDA
+    pub strike_mint: Option<Account<'info, Mint>>,
+
+    #[account(
+        init_if_needed,
+        payer = holder,
+        seeds = [b"strike_escrow", option_contract.key().as_ref()],
+        bump,
+        token::mint = strike_mint,
+        token::authority = option_contract,
+    )]
+    pub strike_escrow: Option<Account<'info, TokenAccount>>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, ProgramConfig>,
+
+    // Required when config.whitelist_enabled: the writer's whitelist entry
+    pub writer_entry: Option<Account<'info, WriterWhitelist>>,
+
+    /// CHECK: writer's EventRegistry PDA for this event — may not exist yet
+    #[account(mut)]
+    pub event_registry: UncheckedAccount<'info>,
+
+    // Required when a protocol fee is configured
+    #[account(mut)]
+    pub treasury_token: Option<Account<'info, TokenAccount>>,
+
+    pub pool: Option<Account<'info, OptionPool>>,
+
+    #[account(
+        init_if_needed,
+        payer = holder,
+        space = HolderStats::MAX_SIZE,
+        seeds = [b"holder", holder.key().as_ref()],
+        bump
+    )]
+    pub holder_stats: Account<'info, HolderStats>,
+
+    #[account(
+        mut,
+        token::mint = premium_mint,
+        token::authority = holder,
+    )]
+    pub holder_token: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub holder: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    /// CHECK: EventStats PDA for the option's event; created on first use
+    #[account(mut)]
+    pub event_stats: UncheckedAccount<'info>,
+
+    /// CHECK: EventIndex PDA for the option's event; created on first use
+    #[account(mut)]
+    pub event_index: UncheckedAccount<'info>,
+
+    #[account(mut, seeds = [b"global_stats"], bump = global_stats.bump)]
+    pub global_stats: Account<'info, GlobalStats>,
+
+    pub system_program: Program<'info, System>,
+
+    pub clock: Option<Sysvar<'info, Clock>>,
+}
+
+#[derive(Accounts)]
+#[instruction(underlying: CreateOptionParams)]
+pub struct CreateCompoundOption<'info> {
+    #[account(
+        init,
+        payer = holder,
+        space = CompoundOption::MAX_SIZE,
+        seeds = [b"compound", underlying.option_id.as_bytes(), &underlying.client_nonce.to_le_bytes()],
+        bump
+    )]
+    pub compound: Account<'info, CompoundOption>,
+
+    // The child option's address, reserved until the compound is used or lapses
+    #[account(
+        init,
+        payer = holder,
+        space = OptionContract::MAX_SIZE,
+        seeds = [b"option", OPTION_SEED_VERSION, underlying.option_id.as_bytes(), &underlying.client_nonce.to_le_bytes()],
+        bump
+    )]
+    pub option_contract: Account<'info, OptionContract>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, ProgramConfig>,
+
+    // Required when config.whitelist_enabled: the writer's whitelist entry
+    pub writer_entry: Option<Account<'info, WriterWhitelist>>,
+
+    /// CHECK: writer's EventRegistry PDA for this event — may not exist yet
+    pub event_registry: UncheckedAccount<'info>,
+
+    /// CHECK: receives the protocol fee only; must match config.treasury
+    #[account(mut, address = config.treasury @ QuorumError::InvalidTreasury)]
+    pub treasury: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub holder: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    pub clock: Option<Sysvar<'info, Clock>>,
+}
+
+#[derive(Accounts)]
+pub struct ExerciseCompoundOption<'info> {
+    #[account(
+        mut,
+        close = holder,
+        seeds = [b"compound", compound.underlying.option_id.as_bytes(), &compound.underlying.client_nonce.to_le_bytes()],
+        bump = compound.bump,
+        has_one = holder @ QuorumError::UnauthorizedHolder,
+        has_one = writer @ QuorumError::InvalidRecipient
+    )]
+    pub compound: Account<'info, CompoundOption>,
+
+    #[account(
+        mut,
+        seeds = [b"option", OPTION_SEED_VERSION, compound.underlying.option_id.as_bytes(), &compound.underlying.client_nonce.to_le_bytes()],
+        bump = option_contract.bump
+    )]
+    pub option_contract: Account<'info, OptionContract>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, ProgramConfig>,
+
+    // Required when config.whitelist_enabled: the writer's whitelist entry
+    pub writer_entry: Option<Account<'info, WriterWhitelist>>,
+
+    /// CHECK: writer's EventRegistry PDA for this event — may not exist yet
+    #[account(mut)]
+    pub event_registry: UncheckedAccount<'info>,
+
+    /// CHECK: receives the protocol fee only; must match config.treasury
+    #[account(mut, address = config.treasury @ QuorumError::InvalidTreasury)]
+    pub treasury: UncheckedAccount<'info>,
+
+    /// CHECK: receives the compound premium only; must match compound.writer
+    #[account(mut)]
+    pub writer: UncheckedAccount<'info>,
+
+    pub pool: Option<Account<'info, OptionPool>>,
+
+    #[account(
+        init_if_needed,
+        payer = holder,
+        space = HolderStats::MAX_SIZE,
+        seeds = [b"holder", holder.key().as_ref()],
+        bump
+    )]
+    pub holder_stats: Account<'info, HolderStats>,
+
+    #[account(mut)]
+    pub holder: Signer<'info>,
+
+    /// CHECK: EventStats PDA for the option's event; created on first use
+    #[account(mut)]
+    pub event_stats: UncheckedAccount<'info>,
+
+    /// CHECK: EventIndex PDA for the option's event; created on first use
+    #[account(mut)]
+    pub event_index: UncheckedAccount<'info>,
+
+    #[account(mut, seeds = [b"global_stats"], bump = global_stats.bump)]
+    pub global_stats: Account<'info, GlobalStats>,
+
+    pub system_program: Program<'info, System>,
+
+    pub clock: Option<Sysvar<'info, Clock>>,
+}
+
+#[derive(Accounts)]
+pub struct ExpireCompoundOption<'info> {
+    #[account(
+        mut,
+        close = holder,
+        seeds = [b"compound", compound.underlying.option_id.as_bytes(), &compound.underlying.client_nonce.to_le_bytes()],
+        bump = compound.bump,
+        has_one = holder @ QuorumError::InvalidRecipient,
+        has_one = writer @ QuorumError::InvalidRecipient
+    )]
+    pub compound: Account<'info, CompoundOption>,
+
+    // The reserved child, released with the compound
+    #[account(
+        mut,
+        close = holder,
+        seeds = [b"option", OPTION_SEED_VERSION, compound.underlying.option_id.as_bytes(), &compound.underlying.client_nonce.to_le_bytes()],
+        bump = option_contract.bump
+    )]
+    pub option_contract: Account<'info, OptionContract>,
+
+    /// CHECK: gets the rent back only; must match compound.holder
+    #[account(mut)]
+    pub holder: UncheckedAccount<'info>,
+
+    /// CHECK: receives the compound premium only; must match compound.writer
+    #[account(mut)]
+    pub writer: UncheckedAccount<'info>,
+
+    pub clock: Option<Sysvar<'info, Clock>>,
+}
+
+#[derive(Accounts)]
+pub struct CreateOptionsBatch<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, ProgramConfig>,
+
+    // Required when config.whitelist_enabled: the writer's whitelist entry
+    pub writer_entry: Option<Account<'info, WriterWhitelist>>,
+
+    /// CHECK: receives the protocol fee only; must match config.treasury
+    #[account(mut, address = config.treasury @ QuorumError::InvalidTreasury)]
+    pub treasury: UncheckedAccount<'info>,
+
+    // Optional shared cap applied to every option in the batch
+    pub pool: Option<Account<'info, OptionPool>>,
+
+    #[account(
+        init_if_needed,
+        payer = holder,
+        space = HolderStats::MAX_SIZE,
+        seeds = [b"holder", holder.key().as_ref()],
+        bump
+    )]
+    pub holder_stats: Account<'info, HolderStats>,
+
+    #[account(mut)]
+    pub holder: Signer<'info>,
+
+    #[account(mut, seeds = [b"global_stats"], bump = global_stats.bump)]
+    pub global_stats: Account<'info, GlobalStats>,
+
+    pub system_program: Program<'info, System>,
+
+    pub clock: Option<Sysvar<'info, Clock>>,
+    // remaining_accounts: per params entry, the writable uninitialized option PDA,
+    // its event's EventStats PDA, the writer's writable EventRegistry PDA for
+    // the event and the event's writable EventIndex PDA
+}
+
+#[derive(Accounts)]
+#[instruction(params: CreateOptionParams)]
+pub struct WriteDutchOption<'info> {
+    #[account(
+        init_if_needed,
+        payer = writer,
+        space = OptionContract::MAX_SIZE,
+        seeds = [b"option", OPTION_SEED_VERSION, params.option_id.as_bytes(), &params.client_nonce.to_le_bytes()],
+        bump
+    )]
+    pub option_contract: Account<'info, OptionContract>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, ProgramConfig>,
+
+    // Required when config.whitelist_enabled: the writer's whitelist entry
+    pub writer_entry: Option<Account<'info, WriterWhitelist>>,
+
+    /// CHECK: writer's EventRegistry PDA for this event — may not exist yet
+    #[account(mut)]
+    pub event_registry: UncheckedAccount<'info>,
+
+    /// CHECK: EventIndex PDA for the option's event; created on first use
+    #[account(mut)]
+    pub event_index: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub writer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    pub clock: Option<Sysvar<'info, Clock>>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimDutchOption<'info> {
+    #[account(
+        mut,
+        seeds = [b"option", OPTION_SEED_VERSION, option_contract.option_id.as_bytes(), &option_contract.client_nonce.to_le_bytes()],
+        bump = option_contract.bump
+    )]
+    pub option_contract: Account<'info, OptionContract>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, ProgramConfig>,
+
+    /// CHECK: receives the protocol fee only; must match config.treasury
+    #[account(mut, address = config.treasury @ QuorumError::InvalidTreasury)]
+    pub treasury: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = holder,
+        space = HolderStats::MAX_SIZE,
+        seeds = [b"holder", holder.key().as_ref()],
+        bump
+    )]
+    pub holder_stats: Account<'info, HolderStats>,
+
+    #[account(mut)]
+    pub holder: Signer<'info>,
+
+    /// CHECK: EventStats PDA for the option's event; created on first use
+    #[account(mut)]
+    pub event_stats: UncheckedAccount<'info>,
+
+    /// CHECK: writer's EventRegistry PDA for the event, for the presale allowlist — may not exist
+    pub event_registry: UncheckedAccount<'info>,
+
+    #[account(mut, seeds = [b"global_stats"], bump = global_stats.bump)]
+    pub global_stats: Account<'info, GlobalStats>,
+
+    pub system_program: Program<'info, System>,
+
+    pub clock: Option<Sysvar<'info, Clock>>,
+}
+
+#[derive(Accounts)]
+#[instruction(option_id: String)]
+pub struct RelistOption<'info> {
+    #[account(
+        mut,
+        seeds = [b"option", OPTION_SEED_VERSION, parent_option.option_id.as_bytes(), &parent_option.client_nonce.to_le_bytes()],
+        bump = parent_option.bump
+    )]
+    pub parent_option: Account<'info, OptionContract>,
+
+    #[account(
+        init_if_needed,
+        payer = reseller,
+        space = OptionContract::MAX_SIZE,
+        seeds = [b"option", OPTION_SEED_VERSION, option_id.as_bytes(), &0u64.to_le_bytes()],
+        bump
+    )]
+    pub option_contract: Account<'info, OptionContract>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, ProgramConfig>,
+
+    // Required when config.whitelist_enabled: the reseller's whitelist entry
+    pub writer_entry: Option<Account<'info, WriterWhitelist>>,
+
+    /// CHECK: reseller's EventRegistry PDA for this event — may not exist
+    #[account(mut)]
+    pub event_registry: UncheckedAccount<'info>,
+
+    /// CHECK: EventIndex PDA for the option's event; created on first use
+    #[account(mut)]
+    pub event_index: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub reseller: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    pub clock: Option<Sysvar<'info, Clock>>,
+}
+
+#[derive(Accounts)]
+pub struct ExerciseOption<'info> {
+    #[account(
+        mut,
+        seeds = [b"option", OPTION_SEED_VERSION, option_contract.option_id.as_bytes(), &option_contract.client_nonce.to_le_bytes()],
+        bump = option_contract.bump
+    )]
+    pub option_contract: Account<'info, OptionContract>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, ProgramConfig>,
+
+    // The holder or their delegate — whoever signs pays the strike. On
+    // exercise_option_relayed this is the relayer, paying for the fan
+    #[account(mut)]
+    pub holder: Signer<'info>,
+
+    // Required when option_contract.pool is set
+    #[account(mut)]
+    pub pool: Option<Account<'info, OptionPool>>,
+
+    // Required when option_contract.nft_mint is set: the signer's token account holding it
+    pub holder_nft: Option<Account<'info, TokenAccount>>,
+
+    // Required when option_contract.identity_hash is set: the exercising wallet's binding
+    pub holder_identity: Option<Account<'info, HolderIdentity>>,
+
+    // strike_mint options only: the strike moves from the signer's token account into escrow
+    #[account(
+        mut,
+        seeds = [b"strike_escrow", option_contract.key().as_ref()],
+        bump
+    )]
+    pub strike_escrow: Option<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub holder_strike_token: Option<Account<'info, TokenAccount>>,
+
+    pub token_program: Option<Program<'info, Token>>,
+
+    /// CHECK: writer's EventRegistry PDA for the event, for royalty tiers — may not exist
+    pub event_registry: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"holder", option_contract.holder.as_ref()],
+        bump = holder_stats.bump
+    )]
+    pub holder_stats: Account<'info, HolderStats>,
+
+    #[account(
+        init_if_needed,
+        payer = holder,
+        space = ExerciseReceipt::MAX_SIZE,
+        seeds = [b"receipt", option_contract.option_id.as_bytes(), &option_contract.client_nonce.to_le_bytes()],
+        bump
+    )]
+    pub receipt: Account<'info, ExerciseReceipt>,
+
+    #[account(mut, seeds = [b"global_stats"], bump = global_stats.bump)]
+    pub global_stats: Account<'info, GlobalStats>,
+
+    pub system_program: Program<'info, System>,
+
+    pub clock: Option<Sysvar<'info, Clock>>,
+}
+
+#[derive(Accounts)]
+pub struct ExpireOption<'info> {
+    #[account(
+        mut,
+        seeds = [b"option", OPTION_SEED_VERSION, option_contract.option_id.as_bytes(), &option_contract.client_nonce.to_le_bytes()],
+        bump = option_contract.bump
+    )]
+    pub option_contract: Account<'info, OptionContract>,
+
+    #[account(
+        mut,
+        seeds = [b"holder", option_contract.holder.as_ref()],
+        bump = holder_stats.bump
+    )]
+    pub holder_stats: Account<'info, HolderStats>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, ProgramConfig>,
+
+    /// CHECK: EventStats PDA for the option's event; created on first use
+    #[account(mut)]
+    pub event_stats: UncheckedAccount<'info>,
+
+    // Pays the keeper reward; omit it, or let it run dry, and expiry is unpaid
+    #[account(mut, seeds = [b"keeper_treasury"], bump = keeper_treasury.bump)]
+    pub keeper_treasury: Option<Account<'info, KeeperTreasury>>,
+
+    // Anyone can call expire — they pay the fee and collect the keeper reward
+    #[account(mut)]
+    pub caller: Signer<'info>,
+
+    #[account(mut, seeds = [b"global_stats"], bump = global_stats.bump)]
+    pub global_stats: Account<'info, GlobalStats>,
+
+    pub system_program: Program<'info, System>,
+
+    pub clock: Option<Sysvar<'info, Clock>>,
+}
+
+#[derive(Accounts)]
+pub struct FundKeeperTreasury<'info> {
+    #[account(
+        init_if_needed,
+        payer = funder,
+        space = KeeperTreasury::MAX_SIZE,
+        seeds = [b"keeper_treasury"],
+        bump
+    )]
+    pub keeper_treasury: Account<'info, KeeperTreasury>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeGlobalStats<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = GlobalStats::MAX_SIZE,
+        seeds = [b"global_stats"],
+        bump
+    )]
+    pub global_stats: Account<'info, GlobalStats>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SettleAtExpiry<'info> {
+    #[account(
+        mut,
+        seeds = [b"option", OPTION_SEED_VERSION, option_contract.option_id.as_bytes(), &option_contract.client_nonce.to_le_bytes()],
+        bump = option_contract.bump
+    )]
+    pub option_contract: Account<'info, OptionContract>,
+
+    #[account(
+        mut,
+        seeds = [b"holder", option_contract.holder.as_ref()],
+        bump = holder_stats.bump
+    )]
+    pub holder_stats: Account<'info, HolderStats>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, ProgramConfig>,
+
+    /// CHECK: writer's EventRegistry PDA for the event — may not exist
+    pub event_registry: UncheckedAccount<'info>,
+
+    // Required when option_contract.pool is set
+    #[account(mut)]
+    pub pool: Option<Account<'info, OptionPool>>,
+
+    #[account(
+        init_if_needed,
+        payer = keeper,
+        space = ExerciseReceipt::MAX_SIZE,
+        seeds = [b"receipt", option_contract.option_id.as_bytes(), &option_contract.client_nonce.to_le_bytes()],
+        bump
+    )]
+    pub receipt: Account<'info, ExerciseReceipt>,
+
+    // Anyone can settle — they pay the fees and the receipt rent
+    #[account(mut)]
+    pub keeper: Signer<'info>,
+
+    #[account(mut, seeds = [b"global_stats"], bump = global_stats.bump)]
+    pub global_stats: Account<'info, GlobalStats>,
+
+    pub system_program: Program<'info, System>,
+
+    pub clock: Option<Sysvar<'info, Clock>>,
+}
+
+#[derive(Accounts)]
+pub struct ConfirmIssuance<'info> {
+    #[account(
+        mut,
+        seeds = [b"option", OPTION_SEED_VERSION, option_contract.option_id.as_bytes(), &option_contract.client_nonce.to_le_bytes()],
+        bump = option_contract.bump
+    )]
+    pub option_contract: Account<'info, OptionContract>,
+
+    // Only the venue can vouch that the tickets exist; it pays for event_stats
+    #[account(mut, address = option_contract.venue @ QuorumError::UnauthorizedVenue)]
+    pub venue: Signer<'info>,
+
+    /// CHECK: receives the strike remainder only; checked in the handler
+    /// against the event's settlement account, or option_contract.writer
+    #[account(mut)]
+    pub settlement_account: UncheckedAccount<'info>,
+
+    /// CHECK: writer's EventRegistry PDA for the event — may not exist
+    pub event_registry: UncheckedAccount<'info>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, ProgramConfig>,
+
+    /// CHECK: receives the exercise fee only; must match config.treasury
+    #[account(mut, address = config.treasury @ QuorumError::InvalidTreasury)]
+    pub treasury: UncheckedAccount<'info>,
+
+    /// CHECK: EventStats PDA for the option's event; created on first use
+    #[account(mut)]
+    pub event_stats: UncheckedAccount<'info>,
+
+    // strike_mint options only: the escrow pays the venue's, treasury's and settlement token accounts
+    #[account(
+        mut,
+        seeds = [b"strike_escrow", option_contract.key().as_ref()],
+        bump
+    )]
+    pub strike_escrow: Option<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub venue_strike_token: Option<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub writer_strike_token: Option<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub treasury_strike_token: Option<Account<'info, TokenAccount>>,
+
+    pub token_program: Option<Program<'info, Token>>,
+
+    #[account(mut, seeds = [b"global_stats"], bump = global_stats.bump)]
+    pub global_stats: Account<'info, GlobalStats>,
+
+    pub system_program: Program<'info, System>,
+
+    pub clock: Option<Sysvar<'info, Clock>>,
+}
+
+#[derive(Accounts)]
+pub struct RefundFailedIssuance<'info> {
+    #[account(
+        mut,
+        seeds = [b"option", OPTION_SEED_VERSION, option_contract.option_id.as_bytes(), &option_contract.client_nonce.to_le_bytes()],
+        bump = option_contract.bump
+    )]
+    pub option_contract: Account<'info, OptionContract>,
+
+    #[account(
+        mut,
+        seeds = [b"receipt", option_contract.option_id.as_bytes(), &option_contract.client_nonce.to_le_bytes()],
+        bump = receipt.bump
+    )]
+    pub receipt: Account<'info, ExerciseReceipt>,
+
+    #[account(
+        mut,
+        seeds = [b"holder", option_contract.holder.as_ref()],
+        bump = holder_stats.bump
+    )]
+    pub holder_stats: Account<'info, HolderStats>,
+
+    /// CHECK: receives the refunded strike only; must match option_contract.pending_payer
+    #[account(mut, address = option_contract.pending_payer @ QuorumError::InvalidRecipient)]
+    pub payer: UncheckedAccount<'info>,
+
+    /// CHECK: receives a returned strike escrow only; must match option_contract.holder
+    #[account(mut, address = option_contract.holder @ QuorumError::InvalidRecipient)]
+    pub holder: UncheckedAccount<'info>,
+
+    // Required when option_contract.pool is set
+    #[account(mut)]
+    pub pool: Option<Account<'info, OptionPool>>,
+
+    // strike_mint options only: the escrow refunds the payer's token account
+    #[account(
+        mut,
+        seeds = [b"strike_escrow", option_contract.key().as_ref()],
+        bump
+    )]
+    pub strike_escrow: Option<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub payer_strike_token: Option<Account<'info, TokenAccount>>,
+
+    #[account(mut, seeds = [b"global_stats"], bump = global_stats.bump)]
+    pub global_stats: Account<'info, GlobalStats>,
+
+    pub token_program: Option<Program<'info, Token>>,
+
+    // Permissionless once the deadline passes
+    pub caller: Signer<'info>,
+
+    pub clock: Option<Sysvar<'info, Clock>>,
+}
+
+#[derive(Accounts)]
+pub struct FreezeOption<'info> {
+    #[account(
+        mut,
+        seeds = [b"option", OPTION_SEED_VERSION, option_contract.option_id.as_bytes(), &option_contract.client_nonce.to_le_bytes()],
+        bump = option_contract.bump
+    )]
+    pub option_contract: Account<'info, OptionContract>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, ProgramConfig>,
+
+    // The program authority or the option's writer
+    pub signer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct PingExpiryWarning<'info> {
+    #[account(
+        mut,
+        seeds = [b"option", OPTION_SEED_VERSION, option_contract.option_id.as_bytes(), &option_contract.client_nonce.to_le_bytes()],
+        bump = option_contract.bump
+    )]
+    pub option_contract: Account<'info, OptionContract>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, ProgramConfig>,
+
+    // Anyone can crank the reminder
+    pub caller: Signer<'info>,
+
+    pub clock: Option<Sysvar<'info, Clock>>,
+}
+
+#[derive(Accounts)]
+pub struct ExpireOptionsBatch<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, ProgramConfig>,
+
+    #[account(mut, seeds = [b"keeper_treasury"], bump = keeper_treasury.bump)]
+    pub keeper_treasury: Option<Account<'info, KeeperTreasury>>,
+
+    #[account(mut)]
+    pub caller: Signer<'info>,
+
+    #[account(mut, seeds = [b"global_stats"], bump = global_stats.bump)]
+    pub global_stats: Account<'info, GlobalStats>,
+
+    pub system_program: Program<'info, System>,
+
+    pub clock: Option<Sysvar<'info, Clock>>,
+    // remaining_accounts: (option, holder_stats, event_stats) triples, all writable
+}
+
+#[derive(Accounts)]
+pub struct ExerciseOptionsBatch<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, ProgramConfig>,
+
+    // Holder or delegate of every option in the batch — pays every strike
+    #[account(mut)]
+    pub holder: Signer<'info>,
+
+    #[account(mut, seeds = [b"global_stats"], bump = global_stats.bump)]
+    pub global_stats: Account<'info, GlobalStats>,
+
+    pub system_program: Program<'info, System>,
+
+    pub clock: Option<Sysvar<'info, Clock>>,
+    // remaining_accounts: (option, holder_stats, receipt, event_registry) quads,
+    // all writable but the registry
+}
+
+#[derive(Accounts)]
+#[instruction(new_holder: Pubkey)]
+pub struct TransferOption<'info> {
+    #[account(
+        mut,
+        seeds = [b"option", OPTION_SEED_VERSION, option_contract.option_id.as_bytes(), &option_contract.client_nonce.to_le_bytes()],
+        bump = option_contract.bump
+    )]
+    pub option_contract: Account<'info, OptionContract>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, ProgramConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"holder", option_contract.holder.as_ref()],
+        bump = from_stats.bump
+    )]
+    pub from_stats: Account<'info, HolderStats>,
+
+    #[account(
+        init_if_needed,
+        payer = holder,
+        space = HolderStats::MAX_SIZE,
+        seeds = [b"holder", new_holder.as_ref()],
+        bump
+    )]
+    pub to_stats: Account<'info, HolderStats>,
+
+    // Required when option_contract.identity_hash is set: new_holder's binding
+    pub to_identity: Option<Account<'info, HolderIdentity>>,
+
+    #[account(mut)]
+    pub holder: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    pub clock: Option<Sysvar<'info, Clock>>,
+}
+
+#[derive(Accounts)]
+pub struct SplitOption<'info> {
+    #[account(
+        mut,
+        seeds = [b"option", OPTION_SEED_VERSION, option_contract.option_id.as_bytes(), &option_contract.client_nonce.to_le_bytes()],
+        bump = option_contract.bump,
+        has_one = creator @ QuorumError::InvalidRecipient,
+        close = creator
+    )]
+    pub option_contract: Account<'info, OptionContract>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, ProgramConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"holder", holder.key().as_ref()],
+        bump
+    )]
+    pub holder_stats: Account<'info, HolderStats>,
+
+    /// CHECK: writer's EventRegistry PDA for the event — may not exist
+    #[account(mut)]
+    pub event_registry: UncheckedAccount<'info>,
+
+    /// CHECK: EventIndex PDA for the option's event; the legs replace the original
+    #[account(mut)]
+    pub event_index: UncheckedAccount<'info>,
+
+    /// CHECK: receives the original's rent only; must match option_contract.creator
+    #[account(mut)]
+    pub creator: UncheckedAccount<'info>,
+
+    // Pays rent for every leg
+    #[account(mut)]
+    pub holder: Signer<'info>,
+
+    #[account(mut, seeds = [b"global_stats"], bump = global_stats.bump)]
+    pub global_stats: Account<'info, GlobalStats>,
+
+    pub system_program: Program<'info, System>,
+
+    pub clock: Option<Sysvar<'info, Clock>>,
+    // remaining_accounts: one uninitialized option PDA per leg, writable
+}
+
+#[derive(Accounts)]
+#[instruction(option_id: String, client_nonce: u64)]
+pub struct MergeOptions<'info> {
+    #[account(
+        init_if_needed,
+        payer = holder,
+        space = OptionContract::MAX_SIZE,
+        seeds = [b"option", OPTION_SEED_VERSION, option_id.as_bytes(), &client_nonce.to_le_bytes()],
+        bump
+    )]
+    pub option_contract: Account<'info, OptionContract>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, ProgramConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"holder", holder.key().as_ref()],
+        bump = holder_stats.bump
+    )]
+    pub holder_stats: Account<'info, HolderStats>,
+
+    /// CHECK: writer's EventRegistry PDA for the event — may not exist
+    #[account(mut)]
+    pub event_registry: UncheckedAccount<'info>,
+
+    /// CHECK: EventIndex PDA for the event; the merged option replaces the originals
+    #[account(mut)]
+    pub event_index: UncheckedAccount<'info>,
+
+    // Pays the merged option's rent
+    #[account(mut)]
+    pub holder: Signer<'info>,
+
+    #[account(mut, seeds = [b"global_stats"], bump = global_stats.bump)]
+    pub global_stats: Account<'info, GlobalStats>,
+
+    pub system_program: Program<'info, System>,
+
+    pub clock: Option<Sysvar<'info, Clock>>,
+    // remaining_accounts: (option, creator) pairs, all writable
+}
+
+#[derive(Accounts)]
+pub struct TransferWriter<'info> {
+    #[account(
+        mut,
+        seeds = [b"option", OPTION_SEED_VERSION, option_contract.option_id.as_bytes(), &option_contract.client_nonce.to_le_bytes()],
+        bump = option_contract.bump,
+        has_one = writer @ QuorumError::UnauthorizedWriter
+    )]
+    pub option_contract: Account<'info, OptionContract>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, ProgramConfig>,
+
+    // Required when config.whitelist_enabled: the new writer's whitelist entry
+    pub new_writer_entry: Option<Account<'info, WriterWhitelist>>,
+
+    pub writer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ExtendExpiry<'info> {
+    #[account(
+        mut,
+        seeds = [b"option", OPTION_SEED_VERSION, option_contract.option_id.as_bytes(), &option_contract.client_nonce.to_le_bytes()],
+        bump = option_contract.bump
+    )]
+    pub option_contract: Account<'info, OptionContract>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, ProgramConfig>,
+
+    #[account(mut)]
+    pub holder: Signer<'info>,
+
+    /// CHECK: EventStats PDA for the option's event; created on first use
+    #[account(mut)]
+    pub event_stats: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    pub clock: Option<Sysvar<'info, Clock>>,
+}
+
+#[derive(Accounts)]
+#[instruction(option_id: String)]
+pub struct MigrateOption<'info> {
+    /// CHECK: pre-versioning option PDA; ownership and contents checked in migrate_option
+    #[account(mut, seeds = [b"option", option_id.as_bytes()], bump)]
+    pub legacy_option: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = holder,
+        space = OptionContract::MAX_SIZE,
+        seeds = [b"option", OPTION_SEED_VERSION, option_id.as_bytes(), &0u64.to_le_bytes()],
+        bump
+    )]
+    pub option_contract: Account<'info, OptionContract>,
+
+    /// CHECK: EventIndex PDA for the option's event; verified in unindex_option/index_option
+    #[account(mut)]
+    pub event_index: UncheckedAccount<'info>,
+
+    // The legacy holder paid the legacy rent, so pays the new rent and gets the old back
+    #[account(mut)]
+    pub holder: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RebalancePremium<'info> {
+    #[account(
+        mut,
+        seeds = [b"option", OPTION_SEED_VERSION, option_contract.option_id.as_bytes(), &option_contract.client_nonce.to_le_bytes()],
+        bump = option_contract.bump,
+        has_one = writer @ QuorumError::UnauthorizedWriter
+    )]
+    pub option_contract: Account<'info, OptionContract>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, ProgramConfig>,
+
+    pub writer: Signer<'info>,
+
+    /// CHECK: receives the refund only; must match option_contract.holder
+    #[account(mut, address = option_contract.holder @ QuorumError::InvalidRecipient)]
+    pub holder: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AddPremium<'info> {
+    #[account(
+        mut,
+        seeds = [b"option", OPTION_SEED_VERSION, option_contract.option_id.as_bytes(), &option_contract.client_nonce.to_le_bytes()],
+        bump = option_contract.bump
+    )]
+    pub option_contract: Account<'info, OptionContract>,
+
+    #[account(mut)]
+    pub holder: Signer<'info>,
+
+    /// CHECK: EventStats PDA for the option's event; created on first use
+    #[account(mut)]
+    pub event_stats: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    pub clock: Option<Sysvar<'info, Clock>>,
+}
+
+#[derive(Accounts)]
+pub struct MintOptionNft<'info> {
+    #[account(
+        mut,
+        seeds = [b"option", OPTION_SEED_VERSION, option_contract.option_id.as_bytes(), &option_contract.client_nonce.to_le_bytes()],
+        bump = option_contract.bump
+    )]
+    pub option_contract: Account<'info, OptionContract>,
+
+    #[account(
+        init,
+        payer = holder,
+        seeds = [b"nft", option_contract.key().as_ref()],
+        bump,
+        mint::decimals = 0,
+        mint::authority = option_contract,
+    )]
+    pub nft_mint: Account<'info, Mint>,
+
+    #[account(
+        init,
+        payer = holder,
+        associated_token::mint = nft_mint,
+        associated_token::authority = holder,
+    )]
+    pub holder_nft: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub holder: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateMetadata<'info> {
+    #[account(
+        mut,
+        seeds = [b"option", OPTION_SEED_VERSION, option_contract.option_id.as_bytes(), &option_contract.client_nonce.to_le_bytes()],
+        bump = option_contract.bump,
+        has_one = writer @ QuorumError::UnauthorizedWriter
+    )]
+    pub option_contract: Account<'info, OptionContract>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, ProgramConfig>,
+
+    /// CHECK: writer's EventRegistry PDA for the (new) event name — may not exist yet
+    pub event_registry: UncheckedAccount<'info>,
+
+    /// CHECK: EventIndex PDA for the current event name; only touched on a rename
+    #[account(mut)]
+    pub old_event_index: UncheckedAccount<'info>,
+
+    /// CHECK: EventIndex PDA for the new event name; only touched on a rename
+    #[account(mut)]
+    pub new_event_index: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub writer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    pub clock: Option<Sysvar<'info, Clock>>,
+}
+
+#[derive(Accounts)]
+pub struct SetMetadataUri<'info> {
+    #[account(
+        mut,
+        seeds = [b"option", OPTION_SEED_VERSION, option_contract.option_id.as_bytes(), &option_contract.client_nonce.to_le_bytes()],
+        bump = option_contract.bump,
+        has_one = writer @ QuorumError::UnauthorizedWriter
+    )]
+    pub option_contract: Account<'info, OptionContract>,
+
+    pub writer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(event_hash: [u8; 32])]
+pub struct PlaceBid<'info> {
+    #[account(
+        init_if_needed,
+        payer = bidder,
+        space = MarketBook::MAX_SIZE,
+        seeds = [b"book", event_hash.as_ref()],
+        bump
+    )]
+    pub book: Account<'info, MarketBook>,
+
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct FillBid<'info> {
+    #[account(
+        mut,
+        seeds = [b"book", book.event_hash.as_ref()],
+        bump = book.bump
+    )]
+    pub book: Account<'info, MarketBook>,
+
+    #[account(
+        mut,
+        seeds = [b"option", OPTION_SEED_VERSION, option_contract.option_id.as_bytes(), &option_contract.client_nonce.to_le_bytes()],
+        bump = option_contract.bump
+    )]
+    pub option_contract: Account<'info, OptionContract>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, ProgramConfig>,
+
+    /// CHECK: only used as the new holder; must match the bid
+    pub bidder: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"holder", option_contract.holder.as_ref()],
+        bump = from_stats.bump
+    )]
+    pub from_stats: Account<'info, HolderStats>,
+
+    #[account(
+        init_if_needed,
+        payer = holder,
+        space = HolderStats::MAX_SIZE,
+        seeds = [b"holder", bidder.key().as_ref()],
+        bump
+    )]
+    pub to_stats: Account<'info, HolderStats>,
+
+    // Required when option_contract.identity_hash is set: the bidder's binding
+    pub bidder_identity: Option<Account<'info, HolderIdentity>>,
+
+    #[account(mut)]
+    pub holder: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    pub clock: Option<Sysvar<'info, Clock>>,
+}
+
+#[derive(Accounts)]
+pub struct CancelBid<'info> {
+    #[account(
+        mut,
+        seeds = [b"book", book.event_hash.as_ref()],
+        bump = book.bump
+    )]
+    pub book: Account<'info, MarketBook>,
+
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetDelegate<'info> {
+    #[account(
+        mut,
+        seeds = [b"option", OPTION_SEED_VERSION, option_contract.option_id.as_bytes(), &option_contract.client_nonce.to_le_bytes()],
+        bump = option_contract.bump
+    )]
+    pub option_contract: Account<'info, OptionContract>,
+
+    pub holder: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct GetDemandScore<'info> {
+    #[account(seeds = [b"stats", event_stats.event_hash.as_ref()], bump = event_stats.bump)]
+    pub event_stats: Account<'info, EventStats>,
+}
+
+#[derive(Accounts)]
+pub struct GetGlobalStats<'info> {
+    #[account(seeds = [b"global_stats"], bump = global_stats.bump)]
+    pub global_stats: Account<'info, GlobalStats>,
+}
+
+#[derive(Accounts)]
+pub struct GetOptionState<'info> {
+    #[account(
+        seeds = [b"option", OPTION_SEED_VERSION, option_contract.option_id.as_bytes(), &option_contract.client_nonce.to_le_bytes()],
+        bump = option_contract.bump
+    )]
+    pub option_contract: Account<'info, OptionContract>,
+
+    pub clock: Option<Sysvar<'info, Clock>>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawExpiredPremium<'info> {
+    #[account(
+        mut,
+        seeds = [b"option", OPTION_SEED_VERSION, option_contract.option_id.as_bytes(), &option_contract.client_nonce.to_le_bytes()],
+        bump = option_contract.bump,
+        has_one = writer @ QuorumError::UnauthorizedWriter
+    )]
+    pub option_contract: Account<'info, OptionContract>,
+
+    #[account(mut)]
+    pub writer: Signer<'info>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, ProgramConfig>,
+
+    #[account(
+        init_if_needed,
+        payer = writer,
+        space = WriterVault::MAX_SIZE,
+        seeds = [b"vault", writer.key().as_ref()],
+        bump
+    )]
+    pub writer_vault: Account<'info, WriterVault>,
+
+    // SPL-premium options only: escrow is swept to the writer's token account
+    #[account(
+        mut,
+        seeds = [b"escrow", option_contract.key().as_ref()],
+        bump
+    )]
+    pub premium_escrow: Option<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub writer_token: Option<Account<'info, TokenAccount>>,
+
+    pub token_program: Option<Program<'info, Token>>,
+
+    pub system_program: Program<'info, System>,
+
+    pub clock: Option<Sysvar<'info, Clock>>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimVested<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", writer.key().as_ref()],
+        bump = writer_vault.bump,
+        has_one = writer @ QuorumError::UnauthorizedWriter
+    )]
+    pub writer_vault: Account<'info, WriterVault>,
+
+    #[account(mut)]
+    pub writer: Signer<'info>,
+
+    pub clock: Option<Sysvar<'info, Clock>>,
+}
+
+#[derive(Accounts)]
+pub struct RaiseDispute<'info> {
+    #[account(
+        mut,
+        seeds = [b"option", OPTION_SEED_VERSION, option_contract.option_id.as_bytes(), &option_contract.client_nonce.to_le_bytes()],
+        bump = option_contract.bump
+    )]
+    pub option_contract: Account<'info, OptionContract>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, ProgramConfig>,
+
+    pub holder: Signer<'info>,
+
+    pub clock: Option<Sysvar<'info, Clock>>,
+}
+
+#[derive(Accounts)]
+pub struct ResolveDispute<'info> {
+    #[account(
+        mut,
+        seeds = [b"option", OPTION_SEED_VERSION, option_contract.option_id.as_bytes(), &option_contract.client_nonce.to_le_bytes()],
+        bump = option_contract.bump,
+        has_one = holder @ QuorumError::InvalidRecipient
+    )]
+    pub option_contract: Account<'info, OptionContract>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = authority @ QuorumError::UnauthorizedAuthority
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    pub authority: Signer<'info>,
+
+    /// CHECK: receives the refund only; must match option_contract.holder
+    #[account(mut)]
+    pub holder: UncheckedAccount<'info>,
+
+    // SPL-premium options only: refund comes out of the escrow
+    #[account(
+        mut,
+        seeds = [b"escrow", option_contract.key().as_ref()],
+        bump
+    )]
+    pub premium_escrow: Option<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub holder_token: Option<Account<'info, TokenAccount>>,
+
+    pub token_program: Option<Program<'info, Token>>,
+}
+
+#[derive(Accounts)]
+pub struct CancelOption<'info> {
+    #[account(
+        mut,
+        seeds = [b"option", OPTION_SEED_VERSION, option_contract.option_id.as_bytes(), &option_contract.client_nonce.to_le_bytes()],
+        bump = option_contract.bump
+    )]
+    pub option_contract: Account<'info, OptionContract>,
+
+    #[account(
+        mut,
+        seeds = [b"holder", option_contract.holder.as_ref()],
+        bump = holder_stats.bump
+    )]
+    pub holder_stats: Account<'info, HolderStats>,
+
+    #[account(mut)]
+    pub holder: Signer<'info>,
+
+    // SPL-premium options only: refund comes out of the escrow
+    #[account(
+        mut,
+        seeds = [b"escrow", option_contract.key().as_ref()],
+        bump
+    )]
+    pub premium_escrow: Option<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub holder_token: Option<Account<'info, TokenAccount>>,
+
+    pub token_program: Option<Program<'info, Token>>,
+
+    /// CHECK: writer's EventRegistry PDA for the event, for its option cap — may not exist
+    #[account(mut)]
+    pub event_registry: UncheckedAccount<'info>,
+
+    #[account(mut, seeds = [b"global_stats"], bump = global_stats.bump)]
+    pub global_stats: Account<'info, GlobalStats>,
+
+    pub clock: Option<Sysvar<'info, Clock>>,
+}
+
+#[derive(Accounts)]
+#[instruction(event_hash: [u8; 32])]
+pub struct UpdateEventRegistry<'info> {
+    #[account(
+        init_if_needed,
+        payer = writer,
+        space = EventRegistry::MAX_SIZE,
+        seeds = [b"event", writer.key().as_ref(), event_hash.as_ref()],
+        bump
+    )]
+    pub registry: Account<'info, EventRegistry>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, ProgramConfig>,
+
+    #[account(mut)]
+    pub writer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    pub clock: Option<Sysvar<'info, Clock>>,
+}
+
+#[derive(Accounts)]
+pub struct FinalizeClearingPrice<'info> {
+    #[account(
+        mut,
+        seeds = [b"event", writer.key().as_ref(), registry.event_hash.as_ref()],
+        bump = registry.bump,
+        has_one = writer @ QuorumError::UnauthorizedWriter
+    )]
+    pub registry: Account<'info, EventRegistry>,
+
+    pub writer: Signer<'info>,
+    // remaining_accounts: (option, holder wallet) pairs, both writable
+}
+
+#[derive(Accounts)]
+pub struct ClaimCancellationRefund<'info> {
+    #[account(
+        mut,
+        seeds = [b"option", OPTION_SEED_VERSION, option_contract.option_id.as_bytes(), &option_contract.client_nonce.to_le_bytes()],
+        bump = option_contract.bump
+    )]
+    pub option_contract: Account<'info, OptionContract>,
+
+    #[account(
+        seeds = [b"event", registry.writer.as_ref(), registry.event_hash.as_ref()],
+        bump = registry.bump
+    )]
+    pub registry: Account<'info, EventRegistry>,
+
+    #[account(
+        mut,
+        seeds = [b"holder", option_contract.holder.as_ref()],
+        bump = holder_stats.bump
+    )]
+    pub holder_stats: Account<'info, HolderStats>,
+
+    #[account(mut)]
+    pub holder: Signer<'info>,
+
+    // SPL-premium options only: refund comes out of the escrow
+    #[account(
+        mut,
+        seeds = [b"escrow", option_contract.key().as_ref()],
+        bump
+    )]
+    pub premium_escrow: Option<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub holder_token: Option<Account<'info, TokenAccount>>,
+
+    #[account(mut, seeds = [b"global_stats"], bump = global_stats.bump)]
+    pub global_stats: Account<'info, GlobalStats>,
+
+    pub token_program: Option<Program<'info, Token>>,
+
+    pub clock: Option<Sysvar<'info, Clock>>,
+}
+
+#[derive(Accounts)]
+pub struct CloseOption<'info> {
+    #[account(
+        mut,
+        seeds = [b"option", OPTION_SEED_VERSION, option_contract.option_id.as_bytes(), &option_contract.client_nonce.to_le_bytes()],
+        bump = option_contract.bump,
+        has_one = writer @ QuorumError::InvalidRecipient,
+        has_one = creator @ QuorumError::InvalidRecipient,
+        close = creator
+    )]
+    pub option_contract: Account<'info, OptionContract>,
+
+    /// CHECK: receives proceeds only; must match option_contract.writer
+    #[account(mut)]
+    pub writer: UncheckedAccount<'info>,
+
+    /// CHECK: receives rent only; must match option_contract.creator
+    #[account(mut)]
+    pub creator: UncheckedAccount<'info>,
+
+    /// CHECK: EventIndex PDA for the option's event; its slot is pruned
+    #[account(mut)]
+    pub event_index: UncheckedAccount<'info>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, ProgramConfig>,
+
+    // SPL-premium options only: escrow is swept to the writer's token account
+    #[account(
+        mut,
+        seeds = [b"escrow", option_contract.key().as_ref()],
+        bump
+    )]
+    pub premium_escrow: Option<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub writer_token: Option<Account<'info, TokenAccount>>,
+
+    // strike_mint options only: closed alongside the premium escrow
+    #[account(
+        mut,
+        seeds = [b"strike_escrow", option_contract.key().as_ref()],
+        bump
+    )]
+    pub strike_escrow: Option<Account<'info, TokenAccount>>,
+
+    pub token_program: Option<Program<'info, Token>>,
+
+    // Anyone can close a settled option — funds only go to writer/creator
+    pub caller: Signer<'info>,
+
+    pub clock: Option<Sysvar<'info, Clock>>,
+}
+
+// ============================================================================
+// EVENTS
+// ============================================================================
+
+#[event]
+pub struct PauseToggled {
+    pub authority: Pubkey,
+    pub paused: bool,
+}
+
+#[event]
+pub struct AuthorityProposed {
+    pub authority: Pubkey,
+    pub pending_authority: Option<Pubkey>, // None = nomination withdrawn
+}
+
+#[event]
+pub struct AuthorityAccepted {
+    pub old_authority: Pubkey,
+    pub new_authority: Pubkey,
+}
+
+#[event]
+pub struct PresaleRootSet {
+    pub writer: Pubkey,
+    pub event_hash: [u8; 32],
+    pub presale_root: Option<[u8; 32]>,
+}
+
+#[event]
+pub struct EventExpirySet {
+    pub writer: Pubkey,
+    pub event_hash: [u8; 32],
+    pub event_expiry: i64,
+}
+
+#[event]
+pub struct EventMaxOptionsSet {
+    pub writer: Pubkey,
+    pub event_hash: [u8; 32],
+    pub max_options: u32,
+    pub options_issued: u32,
+}
+
+#[event]
+pub struct EventLotSizeSet {
+    pub writer: Pubkey,
+    pub event_hash: [u8; 32],
+    pub lot_size: u8,               // 0 or 1 = any quantity
+}
+
+#[event]
+pub struct EventSettlementAccountSet {
+    pub writer: Pubkey,
+    pub event_hash: [u8; 32],
+    pub settlement_account: Pubkey, // default = the option's writer
+}
+
+#[event]
+pub struct IdentityBound {
+    pub venue: Pubkey,
+    pub wallet: Pubkey,
+    pub identity_hash: [u8; 32],
+}
+
+#[event]
+pub struct QuoteSignerSet {
+    pub venue: Pubkey,
+    pub signer: Pubkey,
+}
+
+#[event]
+pub struct WriterWhitelisted {
+    pub writer: Pubkey,
+    pub approved: bool,             // false = removed
+}
+
+#[event]
+pub struct OptionCreated {
+    pub option_id: String,
+    pub event_name: String,
+    pub holder: Pubkey,
+    pub premium_lamports: u64,
+    pub expiry: i64,
+    pub premium_mint: Option<Pubkey>,
+    pub ticket_type: String,
+    pub quantity: u8,
+    pub venue_royalty_bps: u16,
+    pub protocol_fee: u64,          // taken off the top; premium_lamports is net
+    pub metadata_uri: String,       // empty = none
+    // premium_lamports in micro-SOL (1 SOL = 1_000_000), truncated:
+    // divide by 1e6 for SOL, never by 1e9. Always 0 for SPL premiums.
+    pub premium_sol_display: u64,
+}
+
+#[event]
+pub struct DutchOptionListed {
+    pub option_id: String,
+    pub writer: Pubkey,
+    pub premium_start: u64,
+    pub premium_floor: u64,
+    pub decay_end: i64,
+}
+
+#[event]
+pub struct OptionRelisted {
+    pub option_id: String,
+    pub parent_option: Pubkey,
+    pub reseller: Pubkey,
+    pub quantity: u8,
+    pub premium_lamports: u64,
+    pub expiry: i64,
+}
+
+#[event]
+pub struct OptionExercised {
+    pub option_id: String,
+    pub holder: Pubkey,
+    pub strike_lamports: u64,
+    pub quantity: u8,
+}
+
+#[event]
+pub struct OptionPartiallyExercised {
+    pub option_id: String,
+    pub holder: Pubkey,
+    pub strike_lamports: u64,
+    pub exercised_qty: u8,
+    pub remaining_qty: u8,
+}
+
+#[event]
+pub struct IssuanceConfirmed {
+    pub option_id: String,
+    pub venue: Pubkey,
+    pub quantity: u8,
+    pub strike_lamports: u64,
+}
+
+#[event]
+pub struct IssuanceRefunded {
+    pub option_id: String,
+    pub payer: Pubkey,
+    pub quantity: u8,
+    pub strike_lamports: u64,
+}
+
+#[event]
+pub struct RoyaltyPaid {
+    pub option_id: String,
+    pub venue: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct ExerciseFeePaid {
+    pub option_id: String,
+    pub treasury: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct OptionExpired {
+    pub option_id: String,
+    pub holder: Pubkey,
+    pub premium_lamports: u64,      // retained in full
+    pub premium_mint: Option<Pubkey>,
+    pub keeper: Pubkey,
+    pub keeper_reward: u64,
+}
+
+#[event]
+pub struct KeeperTreasuryFunded {
+    pub funder: Pubkey,
+    pub amount: u64,
+    pub balance: u64,               // after this top-up
+}
+
+#[event]
+pub struct OptionCancelled {
+    pub option_id: String,
+    pub holder: Pubkey,
+    pub refund: u64,
+    pub fee_retained: u64,
+}
+
+#[event]
+pub struct EventCancelled {
+    pub writer: Pubkey,
+    pub event_hash: [u8; 32],
+    pub cancelled: bool,
+}
+
+#[event]
+pub struct CancellationRefund {
+    pub option_id: String,
+    pub holder: Pubkey,
+    pub refund: u64,
+}
+
+#[event]
+pub struct TicketTypesSet {
+    pub writer: Pubkey,
+    pub event_hash: [u8; 32],
+    pub ticket_types: Vec<String>,
+}
+
+#[event]
+pub struct RoyaltyTiersSet {
+    pub writer: Pubkey,
+    pub event_hash: [u8; 32],
+    pub royalty_tiers: Vec<RoyaltyTier>,
+}
+
+#[event]
+pub struct ClearingRefund {
+    pub option_id: String,
+    pub holder: Pubkey,
+    pub refund: u64,
+    pub clearing_premium: u64,
+}
+
+#[event]
+pub struct OptionClosed {
+    pub option_id: String,
+    pub writer: Pubkey,
+    pub creator: Pubkey,
+    pub proceeds_lamports: u64,
+}
+
+#[event]
+pub struct OptionExtended {
+    pub option_id: String,
+    pub old_expiry: i64,
+    pub new_expiry: i64,
+    pub additional_premium: u64,
+}
+
+#[event]
+pub struct OptionMigrated {
+    pub option_id: String,
+    pub legacy: Pubkey,
+    pub migrated: Pubkey,
+    pub schema_version: u8,
+}
+
+#[event]
+pub struct PremiumRebalanced {
+    pub option_id: String,
+    pub writer: Pubkey,
+    pub old_premium: u64,
+    pub new_premium: u64,
+    pub refund: u64,
+}
+
+#[event]
+pub struct PremiumIncreased {
+    pub option_id: String,
+    pub holder: Pubkey,
+    pub delta: u64,
+    pub new_total: u64,
+}
+
+#[event]
+pub struct PremiumWithdrawn {
+    pub option_id: String,
+    pub writer: Pubkey,
+    pub amount: u64,
+}
 
-        let clock = Clock::get()?;
-        require!(clock.unix_timestamp > option.expiry, QuorumError::NotExpiredYet);
+#[event]
+pub struct VestedClaimed {
+    pub writer: Pubkey,
+    pub amount: u64,
+    pub still_locked: u64,
+    pub total_claimed: u64,
+}
 
-        option.status = OptionStatus::Expired as u8;
+#[event]
+pub struct OptionNftMinted {
+    pub option_id: String,
+    pub mint: Pubkey,
+    pub holder: Pubkey,
+}
 
-        emit!(OptionExpired {
-            option_id: option.option_id.clone(),
-            holder: option.holder,
-            premium_lamports: option.premium_lamports,
-        });
+#[event]
+pub struct MetadataUpdated {
+    pub option_id: String,
+    pub event_name: String,
+    pub event_date: String,
+    pub ticket_type: String,
+}
 
-        msg!("Option expired: {} — premium retained: {} lamports",
-             option.option_id, option.premium_lamports);
-        Ok(())
-    }
+#[event]
+pub struct MetadataUriUpdated {
+    pub option_id: String,
+    pub metadata_uri: String,       // empty = cleared
 }
 
-// ============================================================================
-// ACCOUNT STRUCTS
-// ============================================================================
+#[event]
+pub struct BidPlaced {
+    pub book: Pubkey,
+    pub bid_id: u64,
+    pub bidder: Pubkey,
+    pub price_lamports: u64,
+    pub min_quantity: u8,
+}
 
-#[account]
-pub struct OptionContract {
-    pub option_id: String,          // unique ID (max 32 chars)
-    pub event_name: String,         // "Florist" (max 64 chars)
-    pub event_date: String,         // "2026-03-01" (max 16 chars)
-    pub ticket_type: String,        // "GA Early Bird" (max 32 chars)
-    pub quantity: u8,               // number of tickets
-    pub premium_lamports: u64,      // premium paid in lamports
-    pub holder: Pubkey,             // fan's wallet
-    pub expiry: i64,                // unix timestamp
-    pub status: u8,                 // 0=Active, 1=Exercised, 2=Expired
-    pub created_at: i64,            // unix timestamp
-    pub venue_royalty_bps: u16,     // basis points (1000 = 10%)
-    pub bump: u8,                   // PDA bump seed
+#[event]
+pub struct BidFilled {
+    pub book: Pubkey,
+    pub bid_id: u64,
+    pub option_id: String,
+    pub seller: Pubkey,
+    pub buyer: Pubkey,
+    pub price_lamports: u64,
 }
 
-impl OptionContract {
-    // 8 discriminator + actual data
-    // Strings: 4 bytes length prefix + content
-    pub const MAX_SIZE: usize = 8
-        + (4 + 32)   // option_id
-        + (4 + 64)   // event_name
-        + (4 + 16)   // event_date
-        + (4 + 32)   // ticket_type
-        + 1          // quantity
-        + 8          // premium_lamports
-        + 32         // holder pubkey
-        + 8          // expiry
-        + 1          // status
-        + 8          // created_at
-        + 2          // venue_royalty_bps
-        + 1;         // bump
+#[event]
+pub struct BidCancelled {
+    pub book: Pubkey,
+    pub bid_id: u64,
+    pub bidder: Pubkey,
+    pub refund: u64,
 }
 
-// Option lifecycle states
-pub enum OptionStatus {
-    Active = 0,
-    Exercised = 1,
-    Expired = 2,
+#[event]
+pub struct DelegateSet {
+    pub option_id: String,
+    pub holder: Pubkey,
+    pub delegate: Option<Pubkey>,   // None = revoked
 }
 
-// ============================================================================
-// CONTEXT STRUCTS
-// ============================================================================
+/// Every SOL transfer, tagged by FlowKind — a full money-movement ledger
+/// from logs alone. option_id is empty for bids not yet matched to an option.
+#[event]
+pub struct LamportFlow {
+    pub option_id: String,
+    pub kind: u8,
+    pub amount: u64,
+    pub from: Pubkey,
+    pub to: Pubkey,
+}
 
-#[derive(Accounts)]
-#[instruction(option_id: String)]
-pub struct CreateOption<'info> {
-    #[account(
-        init,
-        payer = holder,
-        space = OptionContract::MAX_SIZE,
-        seeds = [b"option", option_id.as_bytes()],
-        bump
-    )]
-    pub option_contract: Account<'info, OptionContract>,
+#[event]
+pub struct DisputeRaised {
+    pub option_id: String,
+    pub holder: Pubkey,
+    pub reason: String,
+    pub timestamp: i64,
+}
 
-    #[account(mut)]
-    pub holder: Signer<'info>,
+#[event]
+pub struct DisputeResolved {
+    pub option_id: String,
+    pub holder: Pubkey,
+    pub refunded: bool,
+    pub refund: u64,
+}
 
-    pub system_program: Program<'info, System>,
+#[event]
+pub struct OptionFrozen {
+    pub option_id: String,
+    pub by: Pubkey,
 }
 
-#[derive(Accounts)]
-pub struct ExerciseOption<'info> {
-    #[account(
-        mut,
-        seeds = [b"option", option_contract.option_id.as_bytes()],
-        bump = option_contract.bump
-    )]
-    pub option_contract: Account<'info, OptionContract>,
+#[event]
+pub struct OptionUnfrozen {
+    pub option_id: String,
+    pub by: Pubkey,
+}
 
-    pub holder: Signer<'info>,
+#[event]
+pub struct ExpiryWarning {
+    pub option_id: String,
+    pub holder: Pubkey,
+    pub seconds_left: i64,
 }
 
-#[derive(Accounts)]
-pub struct ExpireOption<'info> {
-    #[account(
-        mut,
-        seeds = [b"option", option_contract.option_id.as_bytes()],
-        bump = option_contract.bump
-    )]
-    pub option_contract: Account<'info, OptionContract>,
+#[event]
+pub struct OptionStatusChanged {
+    pub option_id: String,
+    pub from_status: u8,
+    pub to_status: u8,
+    pub timestamp: i64,
+}
 
-    // Anyone can call expire — no signer constraint needed
-    pub caller: Signer<'info>,
+#[event]
+pub struct OptionTransferred {
+    pub option_id: String,
+    pub from: Pubkey,
+    pub to: Pubkey,
 }
 
-// ============================================================================
-// EVENTS
-// ============================================================================
+#[event]
+pub struct OptionSplit {
+    pub option_id: String,          // shared by every leg
+    pub parent_option: Pubkey,      // closed
+    pub holder: Pubkey,
+    pub children: Vec<Pubkey>,
+    pub quantities: Vec<u8>,        // per child, same order
+}
 
 #[event]
-pub struct OptionCreated {
-    pub option_id: String,
-    pub event_name: String,
+pub struct OptionsMerged {
+    pub option_id: String,          // the merged option's
     pub holder: Pubkey,
+    pub merged_from: Vec<Pubkey>,   // closed
+    pub quantity: u8,
     pub premium_lamports: u64,
+}
+
+#[event]
+pub struct CompoundOptionCreated {
+    pub option_id: String,          // the underlying option's, once exercised
+    pub client_nonce: u64,
+    pub holder: Pubkey,
+    pub writer: Pubkey,
+    pub premium_lamports: u64,      // compound premium, net of the protocol fee
     pub expiry: i64,
+    pub underlying_premium: u64,    // what exercising will cost, before the protocol fee
 }
 
 #[event]
-pub struct OptionExercised {
-    pub option_id: String,
+pub struct CompoundOptionExercised {
+    pub compound: Pubkey,           // closed
+    pub option: Pubkey,             // the new standard option
     pub holder: Pubkey,
+    pub compound_premium: u64,      // paid to the writer
 }
 
 #[event]
-pub struct OptionExpired {
+pub struct CompoundOptionExpired {
+    pub compound: Pubkey,           // closed
+    pub holder: Pubkey,
+    pub writer: Pubkey,
+    pub premium_lamports: u64,      // paid to the writer
+}
+
+#[event]
+pub struct LateExercisePenalty {
     pub option_id: String,
     pub holder: Pubkey,
-    pub premium_lamports: u64,
+    pub writer: Pubkey,             // option's writer; the penalty pays out with the strike remainder on confirm_issuance
+    pub penalty: u64,               // strike units, on top of the strike
+}
+
+#[event]
+pub struct WriterTransferred {
+    pub option_id: String,
+    pub from: Pubkey,
+    pub to: Pubkey,
 }
 
 // ============================================================================
@@ -251,13 +8508,13 @@ pub struct OptionExpired {
 
 #[error_code]
 pub enum QuorumError {
-    #[msg("Quantity must be between 1 and 20")]
+    #[msg("Quantity must be between 1 and the configured max_quantity")]
     InvalidQuantity,
     #[msg("Premium must be greater than 0")]
     InvalidPremium,
     #[msg("String exceeds maximum length")]
     StringTooLong,
-    #[msg("Venue royalty cannot exceed 50%")]
+    #[msg("Venue royalty exceeds config.max_royalty_bps")]
     InvalidRoyalty,
     #[msg("Expiry timestamp must be in the future")]
     ExpiryInPast,
@@ -269,4 +8526,944 @@ pub enum QuorumError {
     OptionExpired,
     #[msg("Option has not expired yet")]
     NotExpiredYet,
+    #[msg("Strike must be greater than 0")]
+    InvalidStrike,
+    #[msg("Option must be Exercised, Expired or Cancelled to close")]
+    NotSettled,
+    #[msg("Recipient does not match the option contract")]
+    InvalidRecipient,
+    #[msg("SPL-premium options require the escrow, token account and token program")]
+    MissingTokenAccounts,
+    #[msg("Cancel refund cannot exceed 100%")]
+    InvalidRefundBps,
+    #[msg("Only the option writer can perform this action")]
+    UnauthorizedWriter,
+    #[msg("Option is not in Expired status")]
+    NotExpired,
+    #[msg("Nothing left to withdraw")]
+    NothingToWithdraw,
+    #[msg("New expiry must be later than the current expiry")]
+    ExpiryNotExtended,
+    #[msg("This instruction only supports SOL-premium options")]
+    NativePremiumOnly,
+    #[msg("Exercise quantity must be between 1 and the remaining quantity")]
+    InvalidExerciseQuantity,
+    #[msg("Program is paused — option creation is frozen")]
+    ProgramPaused,
+    #[msg("Only the program authority can perform this action")]
+    UnauthorizedAuthority,
+    #[msg("An option with this option_id and client_nonce already exists — pick a new ID")]
+    OptionIdTaken,
+    #[msg("Exercise window has not opened yet")]
+    TooEarlyToExercise,
+    #[msg("Exercise window must open before expiry")]
+    InvalidExerciseWindow,
+    #[msg("Batch must contain between 1 and 10 options")]
+    InvalidBatchSize,
+    #[msg("Batch params and remaining accounts must line up one-to-one")]
+    BatchAccountsMismatch,
+    #[msg("Account is not the option PDA for this option_id")]
+    InvalidOptionAccount,
+    #[msg("Pool cap must be greater than 0")]
+    InvalidPoolCap,
+    #[msg("Pool account is missing or does not belong to this option's writer")]
+    InvalidPool,
+    #[msg("Pool exercise cap reached — no inventory left")]
+    PoolCapReached,
+    #[msg("Holder has reached the maximum number of live options")]
+    HolderLimitReached,
+    #[msg("Dutch auction needs 0 < floor <= start and now < decay_end <= expiry")]
+    InvalidDutchAuction,
+    #[msg("Option is not listed for claim")]
+    NotListed,
+    #[msg("option_id and event_name cannot be empty")]
+    EmptyString,
+    #[msg("Treasury account does not match the config")]
+    InvalidTreasury,
+    #[msg("Protocol fee cannot exceed 50%")]
+    InvalidProtocolFee,
+    #[msg("Option is not in Exercised status")]
+    NotExercised,
+    #[msg("No exercised tickets left to relist")]
+    NothingToRelist,
+    #[msg("Exercise cost exceeds max_total_cost")]
+    SlippageExceeded,
+    #[msg("event_date must be a YYYY-MM-DD date")]
+    InvalidEventDate,
+    #[msg("Premium is below the minimum share of face value or config.min_premium_lamports")]
+    PremiumTooLow,
+    #[msg("Minimum premium cannot exceed 100% of face value")]
+    InvalidMinPremium,
+    #[msg("Option has already been (partly) exercised")]
+    AlreadyExercised,
+    #[msg("Option is NFT-backed — it moves with the token, not the holder field")]
+    NftBacked,
+    #[msg("NFT-backed options require the signer's token account for the option mint")]
+    InvalidNftAccount,
+    #[msg("Market book is full — no room for another bid")]
+    BookFull,
+    #[msg("No bid with this ID in the book")]
+    BidNotFound,
+    #[msg("Option is for a different event than this account")]
+    WrongEvent,
+    #[msg("Event has not been cancelled by its writer")]
+    EventNotCancelled,
+    #[msg("Writer is not on the whitelist")]
+    WriterNotWhitelisted,
+    #[msg("Option account holds an unknown status byte")]
+    InvalidStatus,
+    #[msg("Receipt PDA belongs to an earlier option with this option_id")]
+    ReceiptTaken,
+    #[msg("Account is not the EventStats PDA for this option's event")]
+    InvalidEventStats,
+    #[msg("Expiry is sooner than the configured minimum time-to-expiry")]
+    ExpiryTooSoon,
+    #[msg("Clearing premium is already set for this event")]
+    ClearingPriceSet,
+    #[msg("Holder cancelled too recently — wait out the cooldown")]
+    CooldownActive,
+    #[msg("Only the pending authority can accept the handoff")]
+    NotPendingAuthority,
+    #[msg("Arithmetic overflow in lamport math")]
+    MathOverflow,
+    #[msg("ticket_type is not one of the event's registered ticket types")]
+    UnknownTicketType,
+    #[msg("Too many ticket types for one event")]
+    TooManyTicketTypes,
+    #[msg("Account is not the writer's EventRegistry PDA for this event")]
+    InvalidEventRegistry,
+    #[msg("Too many royalty tiers for one event")]
+    TooManyRoyaltyTiers,
+    #[msg("Co-holder shares must be non-zero, unique per wallet, and sum to quantity")]
+    InvalidCoHolders,
+    #[msg("Co-held options can't change hands or be cancelled by the holder")]
+    CoHeld,
+    #[msg("A dispute is open on this option")]
+    DisputeOpen,
+    #[msg("Premium can't be swept until the dispute window passes")]
+    DisputeWindowActive,
+    #[msg("The dispute window for this option has closed")]
+    DisputeWindowClosed,
+    #[msg("No dispute is open on this option")]
+    NotDisputed,
+    #[msg("Account is not the EventIndex PDA for this event")]
+    InvalidEventIndex,
+    #[msg("Event index is full")]
+    EventIndexFull,
+    #[msg("Transfer would drop the account below rent exemption")]
+    InsufficientWithdrawable,
+    #[msg("Auto-exercise is only available on SOL options created by the holder")]
+    AutoExerciseUnsupported,
+    #[msg("Auto-exercise options are settled with settle_at_expiry, not expired")]
+    AutoExercise,
+    #[msg("Option is not set to auto-exercise")]
+    NotAutoExercise,
+    #[msg("The event was cancelled")]
+    EventWasCancelled,
+    #[msg("Premium still backs a live option and can't go to the writer")]
+    PremiumLocked,
+    #[msg("String cap must be non-zero and within the account's reserved space")]
+    InvalidStringLimit,
+    #[msg("Quote doesn't match this venue or these option terms")]
+    InvalidQuote,
+    #[msg("Quote is past its valid_until")]
+    QuoteExpired,
+    #[msg("Nonce has already been used")]
+    QuoteReplayed,
+    #[msg("Missing or mismatched ed25519 signature over the signed message")]
+    InvalidQuoteSignature,
+    #[msg("Claimant is not on this event's presale allowlist")]
+    NotOnAllowlist,
+    #[msg("Another payer's exercise is still awaiting issuance")]
+    IssuancePending,
+    #[msg("Option has no exercise awaiting issuance")]
+    NotPendingIssuance,
+    #[msg("Venue still has time to confirm issuance")]
+    IssuanceTimeoutNotReached,
+    #[msg("Only the option's venue can do this")]
+    UnauthorizedVenue,
+    #[msg("Option is already current or can't be migrated yet")]
+    MigrationUnsupported,
+    #[msg("Only the option's creator can do this")]
+    UnauthorizedCreator,
+    #[msg("Event date has already passed")]
+    EventAlreadyPassed,
+    #[msg("Expired premium vests through the writer vault — withdraw it before closing")]
+    PremiumNotWithdrawn,
+    #[msg("Expiry warning already sent for this expiry")]
+    AlreadyWarned,
+    #[msg("Option isn't within the expiry warning window yet")]
+    NotInWarningWindow,
+    #[msg("Option is frozen")]
+    OptionFrozen,
+    #[msg("Option is already in that freeze state")]
+    FreezeUnchanged,
+    #[msg("Premium is above config.max_premium_lamports")]
+    PremiumTooHigh,
+    #[msg("min_premium_lamports cannot exceed a non-zero max_premium_lamports")]
+    InvalidPremiumBounds,
+    #[msg("Option is non-transferable — only its holder can exercise it")]
+    NonTransferable,
+    #[msg("Event has reached its max_options cap")]
+    EventSoldOut,
+    #[msg("Expiry falls after the event date plus config.event_expiry_buffer_seconds")]
+    ExpiryAfterEvent,
+    #[msg("Illegal option status transition")]
+    InvalidTransition,
+    #[msg("Keeper treasury funding must be greater than zero")]
+    InvalidFundingAmount,
+    #[msg("Nonce account isn't the PDA for this signer and nonce")]
+    InvalidNonceAccount,
+    #[msg("Relayed exercise authorization doesn't match this option")]
+    InvalidRelayAuthorization,
+    #[msg("Relayed exercise authorization is past its valid_until")]
+    AuthorizationExpired,
+    #[msg("Quantity must be a whole number of the event's lot_size")]
+    InvalidLotSize,
+    #[msg("Option is identity-bound — pass the wallet's HolderIdentity")]
+    IdentityRequired,
+    #[msg("HolderIdentity doesn't bind this wallet to the option's identity")]
+    IdentityMismatch,
+    #[msg("Identity-bound options aren't supported on this path")]
+    IdentityUnsupported,
+    #[msg("Split needs 2 to MAX_BATCH_SIZE non-empty legs summing to the option's quantity, on a plain SOL option")]
+    InvalidSplit,
+    #[msg("Merge needs 2 to MAX_BATCH_SIZE distinct plain SOL options")]
+    InvalidMerge,
+    #[msg("Options to merge must share every term but quantity and premium")]
+    TermsMismatch,
+    #[msg("late_exercise_penalty_bps must be at most 10000")]
+    InvalidPenaltyBps,
+    #[msg("Strike payouts must go to the event's settlement account")]
+    InvalidSettlementAccount,
+    #[msg("Compound expiry must be in the future and no later than the underlying option's")]
+    InvalidCompoundExpiry,
+    #[msg("Option account is not a compound option's reserved placeholder")]
+    NotReserved,
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::solana_program::program_stubs;
+
+    const ALL_STATUSES: [OptionStatus; 7] = [
+        OptionStatus::Active,
+        OptionStatus::Exercised,
+        OptionStatus::Expired,
+        OptionStatus::Cancelled,
+        OptionStatus::Listed,
+        OptionStatus::PendingIssuance,
+        OptionStatus::Reserved,
+    ];
+
+    #[test]
+    fn can_transition_matrix() {
+        use OptionStatus::*;
+        let legal = [
+            (Listed, Active),
+            (Active, PendingIssuance),
+            (Active, Expired),
+            (Active, Cancelled),
+            (PendingIssuance, Exercised),
+            (PendingIssuance, Active),
+            (Expired, Cancelled),
+        ];
+        for from in ALL_STATUSES {
+            for to in ALL_STATUSES {
+                assert_eq!(
+                    can_transition(from, to),
+                    legal.contains(&(from, to)),
+                    "{:?} -> {:?}",
+                    from,
+                    to
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn status_round_trips_through_u8() {
+        for status in ALL_STATUSES {
+            assert_eq!(OptionStatus::try_from(status as u8).unwrap(), status);
+        }
+        assert!(OptionStatus::try_from(ALL_STATUSES.len() as u8).is_err());
+    }
+
+    fn refundable_option() -> OptionContract {
+        OptionContract {
+            premium_lamports: 1_000_000,
+            cancel_refund_bps: 8_000,
+            refund_decay_start: 1_000,
+            expiry: 2_000,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn cancel_refund_decays_over_the_window() {
+        let option = refundable_option();
+        assert_eq!(cancel_refund(&option, 1_000), 800_000);
+        assert_eq!(cancel_refund(&option, 1_500), 400_000);
+        assert_eq!(cancel_refund(&option, 2_000), 0);
+    }
+
+    #[test]
+    fn cancel_refund_clamps_outside_the_window() {
+        let option = refundable_option();
+        assert_eq!(cancel_refund(&option, 0), 800_000);
+        assert_eq!(cancel_refund(&option, 3_000), 0);
+        let spent = OptionContract {
+            refund_decay_start: 2_000,
+            ..refundable_option()
+        };
+        assert_eq!(cancel_refund(&spent, 1_500), 0);
+    }
+
+    #[test]
+    fn is_iso_date_accepts_calendar_dates() {
+        for date in ["2026-03-01", "2026-12-31", "2024-02-29", "2000-02-29", "1970-01-01"] {
+            assert!(is_iso_date(date), "{}", date);
+        }
+    }
+
+    #[test]
+    fn is_iso_date_rejects_malformed_dates() {
+        for date in [
+            "",
+            "2026-3-01",
+            "2026-03-1",
+            "2026-03-011",
+            "not a date",
+            "2026/03/01",
+            "2026-03/01",
+            "20260-3-01",
+            "2026-0a-01",
+            "2026-00-10",
+            "2026-13-10",
+            "2026-03-00",
+            "2026-03-32",
+            "2026-04-31",
+            "2026-02-29",
+            "1900-02-29",
+        ] {
+            assert!(!is_iso_date(date), "{}", date);
+        }
+    }
+
+    #[test]
+    fn iso_date_timestamp_is_utc_midnight() {
+        assert_eq!(iso_date_timestamp("1970-01-01"), 0);
+        assert_eq!(iso_date_timestamp("2000-03-01"), 951_868_800);
+        assert_eq!(iso_date_timestamp("2024-02-29"), 1_709_164_800);
+        assert_eq!(iso_date_timestamp("2026-03-01"), 1_772_323_200);
+        assert_eq!(iso_date_timestamp("1969-12-31"), -86_400);
+    }
+
+    #[test]
+    fn normalize_event_name_folds_case() {
+        assert_eq!(normalize_event_name("Florist LIVE"), "florist live");
+        assert_eq!(normalize_event_name("florist live"), "florist live");
+    }
+
+    #[test]
+    fn normalize_event_name_collapses_and_trims_whitespace() {
+        assert_eq!(normalize_event_name("  Florist\t LIVE "), "florist live");
+        assert_eq!(normalize_event_name("Florist\n\nLive"), "florist live");
+        assert_eq!(normalize_event_name("   "), "");
+    }
+
+    #[test]
+    fn event_seed_keys_on_the_normalized_name() {
+        assert_eq!(event_seed("  Florist\t LIVE "), event_seed("florist live"));
+        assert_ne!(event_seed("Florist"), event_seed("Florist Live"));
+    }
+
+    /// 00:00 UTC on the fixture's event_date, and a "now" ten days out.
+    const EVENT_START: i64 = 1_772_323_200;
+    const NOW: i64 = EVENT_START - 10 * 86_400;
+
+    /// The config initialize_config writes.
+    fn test_config() -> ProgramConfig {
+        ProgramConfig {
+            authority: Pubkey::default(),
+            paused: false,
+            bump: 0,
+            max_per_holder: 0,
+            protocol_fee_bps: 0,
+            treasury: Pubkey::default(),
+            expire_reward_lamports: 0,
+            min_premium_bps: 0,
+            max_quantity: DEFAULT_MAX_QUANTITY,
+            grace_seconds: 0,
+            whitelist_enabled: false,
+            min_expiry_seconds: 0,
+            action_cooldown_seconds: 0,
+            pending_authority: None,
+            dispute_window_seconds: 0,
+            max_option_id_len: MAX_OPTION_ID_LEN,
+            max_event_name_len: MAX_EVENT_NAME_LEN,
+            max_event_date_len: MAX_EVENT_DATE_LEN,
+            max_ticket_type_len: MAX_TICKET_TYPE_LEN,
+            issuance_timeout_seconds: DEFAULT_ISSUANCE_TIMEOUT_SECONDS,
+            max_royalty_bps: DEFAULT_MAX_ROYALTY_BPS,
+            writer_vesting_seconds: 0,
+            expiry_warning_seconds: DEFAULT_EXPIRY_WARNING_SECONDS,
+            min_premium_lamports: 0,
+            max_premium_lamports: 0,
+            exercise_fee_bps: 0,
+            event_expiry_buffer_seconds: DEFAULT_EVENT_EXPIRY_BUFFER_SECONDS,
+            late_exercise_penalty_bps: 0,
+        }
+    }
+
+    fn test_params() -> CreateOptionParams {
+        CreateOptionParams {
+            option_id: "florist-ga-1".to_string(),
+            event_name: "Florist".to_string(),
+            event_date: "2026-03-01".to_string(),
+            ticket_type: "GA".to_string(),
+            quantity: 2,
+            premium_lamports: 50_000_000,
+            strike_lamports: 1_000_000_000,
+            expiry: EVENT_START,
+            venue_royalty_bps: 1_000,
+            cancel_refund_bps: 5_000,
+            writer: None,
+            venue: None,
+            exercise_window_start: 0,
+            co_holders: vec![],
+            auto_exercise: false,
+            client_nonce: 0,
+            transferable: None,
+            metadata_uri: None,
+            identity_hash: None,
+        }
+    }
+
+    fn assert_err<T: std::fmt::Debug>(result: Result<T>, expected: QuorumError) {
+        assert_eq!(result.unwrap_err(), Error::from(expected));
+    }
+
+    #[test]
+    fn validate_option_terms_accepts_the_fixture() {
+        validate_option_terms(&test_params(), &test_config(), NOW).unwrap();
+    }
+
+    #[test]
+    fn validate_option_terms_rejects_empty_strings() {
+        let config = test_config();
+        let params = CreateOptionParams {
+            option_id: String::new(),
+            ..test_params()
+        };
+        assert_err(validate_option_terms(&params, &config, NOW), QuorumError::EmptyString);
+        let params = CreateOptionParams {
+            event_name: String::new(),
+            ..test_params()
+        };
+        assert_err(validate_option_terms(&params, &config, NOW), QuorumError::EmptyString);
+    }
+
+    #[test]
+    fn confirming_an_issuance_twice_finds_nothing_pending() {
+        let mut option = OptionContract {
+            status: OptionStatus::PendingIssuance as u8,
+            ..Default::default()
+        };
+        hold_for_issuance(&mut option, Pubkey::new_unique(), None, false, 1_000, 100, 10, 2, 50).unwrap();
+
+        let payout = take_pending_issuance(&mut option).unwrap();
+        assert_eq!(
+            (payout.strike, payout.royalty, payout.fee, payout.writer_share, payout.quantity),
+            (1_000, 100, 10, 890, 2)
+        );
+        assert!(payout.fully_exercised);
+        // State is already settled before any payout would run
+        assert_eq!(option.status_enum().unwrap(), OptionStatus::Exercised);
+        assert_eq!(option.pending_strike, 0);
+        assert_err(take_pending_issuance(&mut option), QuorumError::NotPendingIssuance);
+    }
+
+    #[test]
+    fn confirming_a_partial_exercise_leaves_the_option_active() {
+        let mut option = OptionContract {
+            status: OptionStatus::Active as u8,
+            quantity: 3,
+            ..Default::default()
+        };
+        hold_for_issuance(&mut option, Pubkey::new_unique(), None, false, 1_000, 0, 0, 1, 50).unwrap();
+        let payout = take_pending_issuance(&mut option).unwrap();
+        assert!(!payout.fully_exercised);
+        assert_eq!(option.status_enum().unwrap(), OptionStatus::Active);
+        assert_err(take_pending_issuance(&mut option), QuorumError::NotPendingIssuance);
+    }
+
+    #[test]
+    fn hold_for_issuance_stacks_only_the_same_payer() {
+        let (payer, other) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut option = OptionContract::default();
+        hold_for_issuance(&mut option, payer, None, false, 1_000, 100, 10, 1, 50).unwrap();
+        hold_for_issuance(&mut option, payer, None, false, 1_000, 100, 10, 1, 60).unwrap();
+        assert_eq!(
+            (option.pending_strike, option.pending_royalty, option.pending_fee, option.pending_qty),
+            (2_000, 200, 20, 2)
+        );
+        assert_eq!(option.issuance_deadline, 60);
+        assert_err(
+            hold_for_issuance(&mut option, other, None, false, 1_000, 100, 10, 1, 60),
+            QuorumError::IssuancePending,
+        );
+        assert_err(
+            hold_for_issuance(&mut option, payer, Some(other), false, 1_000, 100, 10, 1, 60),
+            QuorumError::IssuancePending,
+        );
+        clear_pending_issuance(&mut option);
+        hold_for_issuance(&mut option, other, None, false, 1_000, 100, 10, 1, 70).unwrap();
+        assert_eq!(option.pending_payer, other);
+    }
+
+    #[test]
+    fn validate_option_terms_caps_quantity_at_config_max() {
+        let config = ProgramConfig {
+            max_quantity: 50,
+            ..test_config()
+        };
+        let at_max = CreateOptionParams {
+            quantity: 50,
+            ..test_params()
+        };
+        validate_option_terms(&at_max, &config, NOW).unwrap();
+        let over = CreateOptionParams {
+            quantity: 51,
+            ..test_params()
+        };
+        assert_err(validate_option_terms(&over, &config, NOW), QuorumError::InvalidQuantity);
+        let none = CreateOptionParams {
+            quantity: 0,
+            ..test_params()
+        };
+        assert_err(validate_option_terms(&none, &config, NOW), QuorumError::InvalidQuantity);
+    }
+
+    #[test]
+    fn validate_option_terms_enforces_min_expiry_at_the_boundary() {
+        let config = ProgramConfig {
+            min_expiry_seconds: 3_600,
+            ..test_config()
+        };
+        let with_expiry = |expiry| CreateOptionParams {
+            expiry,
+            ..test_params()
+        };
+        validate_option_terms(&with_expiry(NOW + 3_600), &config, NOW).unwrap();
+        assert_err(
+            validate_option_terms(&with_expiry(NOW + 3_599), &config, NOW),
+            QuorumError::ExpiryTooSoon,
+        );
+        assert_err(validate_option_terms(&with_expiry(NOW), &config, NOW), QuorumError::ExpiryInPast);
+    }
+
+    #[test]
+    fn lamport_math_near_u64_max_errors_instead_of_panicking() {
+        let params = CreateOptionParams {
+            auto_exercise: true,
+            strike_lamports: u64::MAX / 2 + 1,
+            quantity: 2,
+            ..test_params()
+        };
+        assert_err(strike_escrow_for(&params), QuorumError::MathOverflow);
+
+        let mut option = OptionContract {
+            pending_strike: u64::MAX,
+            ..Default::default()
+        };
+        assert_err(
+            hold_for_issuance(&mut option, Pubkey::default(), None, false, 1, 0, 0, 1, 0),
+            QuorumError::MathOverflow,
+        );
+        // Royalty and fee can't add up past the strike they come out of
+        let mut option = OptionContract::default();
+        assert_err(
+            hold_for_issuance(&mut option, Pubkey::default(), None, false, 100, u64::MAX, 1, 1, 0),
+            QuorumError::MathOverflow,
+        );
+        assert_err(
+            hold_for_issuance(&mut option, Pubkey::default(), None, false, 100, 60, 41, 1, 0),
+            QuorumError::MathOverflow,
+        );
+
+        let mut vault = WriterVault {
+            writer: Pubkey::default(),
+            bump: 0,
+            locked: u64::MAX,
+            unlocked: 0,
+            last_update: NOW,
+            vesting_end: NOW + 100,
+            total_claimed: 0,
+        };
+        assert_err(
+            deposit_writer_vault(&mut vault, 1, &test_config(), NOW),
+            QuorumError::MathOverflow,
+        );
+    }
+
+    /// Serves Rent::default() to Rent::get(), which has no sysvar off-chain.
+    struct RentStub;
+
+    impl program_stubs::SyscallStubs for RentStub {
+        fn sol_get_rent_sysvar(&self, var_addr: *mut u8) -> u64 {
+            unsafe { *(var_addr as *mut Rent) = Rent::default() };
+            anchor_lang::solana_program::entrypoint::SUCCESS
+        }
+    }
+
+    #[test]
+    fn transfer_from_pda_never_dips_into_rent() {
+        program_stubs::set_syscall_stubs(Box::new(RentStub));
+        let rent_floor = Rent::default().minimum_balance(100);
+        let (from_key, to_key, owner) = (Pubkey::new_unique(), Pubkey::new_unique(), crate::ID);
+        let (mut from_lamports, mut to_lamports) = (rent_floor + 500, 0);
+        let (mut from_data, mut to_data) = ([0u8; 100], [0u8; 0]);
+        let from = AccountInfo::new(&from_key, false, true, &mut from_lamports, &mut from_data, &owner, false, 0);
+        let to = AccountInfo::new(&to_key, false, true, &mut to_lamports, &mut to_data, &owner, false, 0);
+
+        assert_eq!(withdrawable_lamports(&from).unwrap(), 500);
+        assert_err(transfer_from_pda(&from, &to, 501), QuorumError::InsufficientWithdrawable);
+        transfer_from_pda(&from, &to, 500).unwrap();
+        assert_eq!((from.lamports(), to.lamports()), (rent_floor, 500));
+        assert_eq!(withdrawable_lamports(&from).unwrap(), 0);
+    }
+
+    #[test]
+    fn writer_payout_waits_until_the_holder_is_done() {
+        let with_status = |status: OptionStatus| OptionContract {
+            status: status as u8,
+            premium_lamports: 1_000,
+            ..Default::default()
+        };
+        for status in [OptionStatus::Active, OptionStatus::PendingIssuance, OptionStatus::Listed] {
+            assert_err(require_writer_payout_allowed(&with_status(status)), QuorumError::PremiumLocked);
+        }
+        for status in [OptionStatus::Expired, OptionStatus::Exercised, OptionStatus::Cancelled] {
+            require_writer_payout_allowed(&with_status(status)).unwrap();
+        }
+        // An unclaimed listing never took a premium
+        let listed = OptionContract {
+            premium_lamports: 0,
+            ..with_status(OptionStatus::Listed)
+        };
+        require_writer_payout_allowed(&listed).unwrap();
+
+        let escrowed = OptionContract {
+            strike_escrowed: 1,
+            ..with_status(OptionStatus::Expired)
+        };
+        assert_err(require_writer_payout_allowed(&escrowed), QuorumError::PremiumLocked);
+        let pending = OptionContract {
+            pending_strike: 1,
+            ..with_status(OptionStatus::Exercised)
+        };
+        assert_err(require_writer_payout_allowed(&pending), QuorumError::IssuancePending);
+        let frozen = OptionContract {
+            frozen: true,
+            ..with_status(OptionStatus::Expired)
+        };
+        assert_err(require_writer_payout_allowed(&frozen), QuorumError::OptionFrozen);
+    }
+
+    #[test]
+    fn validate_option_terms_caps_royalty_at_config_ceiling() {
+        let config = ProgramConfig {
+            max_royalty_bps: 2_000,
+            ..test_config()
+        };
+        let with_royalty = |venue_royalty_bps| CreateOptionParams {
+            venue_royalty_bps,
+            ..test_params()
+        };
+        validate_option_terms(&with_royalty(1_999), &config, NOW).unwrap();
+        validate_option_terms(&with_royalty(2_000), &config, NOW).unwrap();
+        assert_err(
+            validate_option_terms(&with_royalty(2_001), &config, NOW),
+            QuorumError::InvalidRoyalty,
+        );
+    }
+
+    #[test]
+    fn premium_bounds_hold_at_the_boundaries() {
+        let config = ProgramConfig {
+            min_premium_lamports: 1_000,
+            max_premium_lamports: 5_000,
+            ..test_config()
+        };
+        assert_err(require_premium_bounds(&config, 999), QuorumError::PremiumTooLow);
+        require_premium_bounds(&config, 1_000).unwrap();
+        require_premium_bounds(&config, 5_000).unwrap();
+        assert_err(require_premium_bounds(&config, 5_001), QuorumError::PremiumTooHigh);
+    }
+
+    #[test]
+    fn zero_premium_bounds_mean_unbounded() {
+        let config = test_config();
+        require_premium_bounds(&config, 1).unwrap();
+        require_premium_bounds(&config, u64::MAX).unwrap();
+    }
+
+    #[test]
+    fn exercise_fee_is_independent_of_royalty() {
+        let config = ProgramConfig {
+            exercise_fee_bps: 250,
+            ..test_config()
+        };
+        let strike = 2_000_000_000;
+        assert_eq!(exercise_fee(&config, strike), 50_000_000);
+        assert_eq!(exercise_fee(&test_config(), strike), 0);
+
+        for royalty_bps in [0u64, 1_000, 5_000] {
+            let royalty = strike * royalty_bps / 10_000;
+            let fee = exercise_fee(&config, strike);
+            let mut option = OptionContract::default();
+            hold_for_issuance(&mut option, Pubkey::default(), None, false, strike, royalty, fee, 2, 0).unwrap();
+            assert_eq!(option.pending_fee, 50_000_000);
+            // What confirm_issuance pays out: holder cost = royalty + fee + writer share
+            let writer_share = option.pending_strike - option.pending_royalty - option.pending_fee;
+            assert_eq!(option.pending_royalty + option.pending_fee + writer_share, strike);
+        }
+    }
+
+    #[test]
+    fn expiry_may_not_outrun_the_event() {
+        let config = test_config();
+        let buffer = DEFAULT_EVENT_EXPIRY_BUFFER_SECONDS as i64;
+        require_event_upcoming(&config, "2026-03-01", EVENT_START + buffer, NOW).unwrap();
+        assert_err(
+            require_event_upcoming(&config, "2026-03-01", EVENT_START + buffer + 1, NOW),
+            QuorumError::ExpiryAfterEvent,
+        );
+        // One day past the show with no buffer
+        let config = ProgramConfig {
+            event_expiry_buffer_seconds: 0,
+            ..test_config()
+        };
+        assert_err(
+            require_event_upcoming(&config, "2026-03-01", EVENT_START + 86_400, NOW),
+            QuorumError::ExpiryAfterEvent,
+        );
+    }
+
+    #[test]
+    fn event_day_must_not_be_over() {
+        let config = test_config();
+        require_event_upcoming(&config, "2026-03-01", EVENT_START, EVENT_START + 86_399).unwrap();
+        assert_err(
+            require_event_upcoming(&config, "2026-03-01", EVENT_START, EVENT_START + 86_400),
+            QuorumError::EventAlreadyPassed,
+        );
+    }
+
+    #[test]
+    fn canonical_option_bump_rejects_a_ground_bump() {
+        let (option_id, nonce) = ("florist-ga-1", 7u64);
+        let seeds: [&[u8]; 4] = [b"option", OPTION_SEED_VERSION, option_id.as_bytes(), &nonce.to_le_bytes()];
+        let (canonical, bump) = Pubkey::find_program_address(&seeds, &ID);
+        assert_eq!(canonical_option_bump(option_id, nonce, canonical, &ID).unwrap(), bump);
+
+        // The next bump down that still lands off-curve is a valid, non-canonical PDA
+        let ground = (0..bump)
+            .rev()
+            .find_map(|b| Pubkey::create_program_address(&[seeds[0], seeds[1], seeds[2], seeds[3], &[b]], &ID).ok())
+            .expect("some lower bump is off-curve");
+        assert_err(
+            canonical_option_bump(option_id, nonce, ground, &ID),
+            QuorumError::InvalidOptionAccount,
+        );
+    }
+
+    fn mergeable_option() -> OptionContract {
+        OptionContract {
+            event_name: "Florist".to_string(),
+            event_date: "2026-03-01".to_string(),
+            ticket_type: "GA".to_string(),
+            strike_lamports: 1_000_000_000,
+            expiry: EVENT_START,
+            writer: Pubkey::new_from_array([1; 32]),
+            venue: Pubkey::new_from_array([2; 32]),
+            venue_royalty_bps: 1_000,
+            transferable: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn same_terms_ignores_quantity_and_premium() {
+        let a = OptionContract {
+            quantity: 2,
+            premium_lamports: 10,
+            ..mergeable_option()
+        };
+        let b = OptionContract {
+            quantity: 5,
+            premium_lamports: 99,
+            holder: Pubkey::new_unique(),
+            ..mergeable_option()
+        };
+        assert!(same_terms(&a, &b));
+    }
+
+    #[test]
+    fn same_terms_rejects_mismatched_terms() {
+        let base = mergeable_option();
+        let mismatches = [
+            OptionContract { ticket_type: "VIP".to_string(), ..mergeable_option() },
+            OptionContract { strike_lamports: 1, ..mergeable_option() },
+            OptionContract { expiry: EVENT_START - 1, ..mergeable_option() },
+            OptionContract { venue: Pubkey::new_unique(), ..mergeable_option() },
+            OptionContract { venue_royalty_bps: 0, ..mergeable_option() },
+            OptionContract { auto_exercise: true, ..mergeable_option() },
+            OptionContract { transferable: false, ..mergeable_option() },
+            OptionContract { identity_hash: Some([3; 32]), ..mergeable_option() },
+        ];
+        for other in &mismatches {
+            assert!(!same_terms(&base, other));
+            assert!(!same_terms(other, &base));
+        }
+    }
+
+    #[test]
+    fn late_exercise_penalty_applies_only_after_expiry() {
+        let config = ProgramConfig {
+            late_exercise_penalty_bps: 500,
+            ..test_config()
+        };
+        let option = OptionContract {
+            expiry: EVENT_START,
+            ..Default::default()
+        };
+        let strike = 1_000_000_000;
+        assert_eq!(late_exercise_penalty(&config, &option, strike, EVENT_START), 0);
+        assert_eq!(late_exercise_penalty(&config, &option, strike, EVENT_START + 1), 50_000_000);
+        assert_eq!(late_exercise_penalty(&test_config(), &option, strike, EVENT_START + 1), 0);
+        // A prefunded auto-exercise strike was paid on time
+        let prefunded = OptionContract {
+            strike_escrowed: strike,
+            ..option
+        };
+        assert_eq!(late_exercise_penalty(&config, &prefunded, strike, EVENT_START + 1), 0);
+    }
+
+    #[test]
+    fn dutch_premium_decays_linearly_to_the_floor() {
+        let option = OptionContract {
+            premium_start: 1_000,
+            premium_floor: 200,
+            created_at: 100,
+            decay_end: 200,
+            ..Default::default()
+        };
+        assert_eq!(dutch_premium(&option, 100), 1_000);
+        assert_eq!(dutch_premium(&option, 150), 600);
+        assert_eq!(dutch_premium(&option, 200), 200);
+        assert_eq!(dutch_premium(&option, 500), 200);
+        assert_eq!(dutch_premium(&option, 0), 1_000);
+    }
+
+    #[test]
+    fn record_demand_averages_premium_to_face_value() {
+        let mut stats = EventStats::default();
+        // 10% of face value sets the score outright
+        record_demand(&mut stats, 200, 1_000, 2);
+        assert_eq!((stats.demand_score, stats.demand_samples), (100_000, 1));
+        // Then a 1/DEMAND_SCORE_WINDOW step toward each new ratio
+        record_demand(&mut stats, 2_000, 1_000, 2);
+        assert_eq!(stats.demand_score, (100_000 * 15 + 1_000_000 + 8) / 16);
+        assert_eq!(stats.demand_samples, 2);
+        // Free tickets have no ratio
+        record_demand(&mut stats, 500, 0, 2);
+        assert_eq!(stats.demand_samples, 2);
+    }
+
+    #[test]
+    fn record_price_overwrites_the_oldest_sample() {
+        let mut stats = EventStats::default();
+        for i in 0..PRICE_HISTORY_LEN as i64 + 3 {
+            record_price(&mut stats, i, i as u64 * 10);
+        }
+        assert_eq!(stats.price_history_count as usize, PRICE_HISTORY_LEN);
+        assert_eq!(stats.price_history_head, 3);
+        // The head is the oldest surviving sample; the slot before it the newest
+        assert_eq!(stats.price_history[3].timestamp, 3);
+        assert_eq!(stats.price_history[2].timestamp, PRICE_HISTORY_LEN as i64 + 2);
+        assert_eq!(stats.price_history[2].premium, (PRICE_HISTORY_LEN as u64 + 2) * 10);
+    }
+
+    #[test]
+    fn legacy_v0_options_map_onto_the_current_layout() {
+        let holder = Pubkey::new_unique();
+        let legacy = LegacyOptionContractV0 {
+            option_id: "o".repeat(32),
+            event_name: "e".repeat(64),
+            event_date: "2026-03-01".to_string(),
+            ticket_type: "t".repeat(32),
+            quantity: 4,
+            premium_lamports: 50_000_000,
+            holder,
+            expiry: EVENT_START,
+            status: OptionStatus::Active as u8,
+            created_at: NOW,
+            venue_royalty_bps: 1_000,
+            bump: 254,
+        };
+        // A v0 account as written: discriminator, fields, zero padding to its
+        // MAX_SIZE — strings at their caps leave no padding at all
+        let mut data = OptionContract::DISCRIMINATOR.to_vec();
+        legacy.serialize(&mut data).unwrap();
+        let v0_size = 8 + (4 + 32) + (4 + 64) + (4 + 16) + (4 + 32) + 1 + 8 + 32 + 8 + 1 + 8 + 2 + 1;
+        data.resize(v0_size, 0);
+
+        let decoded = LegacyOptionContractV0::deserialize(&mut &data[8..]).unwrap();
+        let option = decoded.into_current(253);
+        assert_eq!(option.option_id, "o".repeat(32));
+        assert_eq!(option.event_name, "e".repeat(64));
+        assert_eq!(option.ticket_type, "t".repeat(32));
+        assert_eq!((option.quantity, option.premium_lamports), (4, 50_000_000));
+        assert_eq!((option.expiry, option.created_at), (EVENT_START, NOW));
+        assert_eq!(option.status_enum().unwrap(), OptionStatus::Active);
+        assert_eq!(option.venue_royalty_bps, 1_000);
+        assert_eq!(option.bump, 253);
+        // The holder stood in for every party
+        assert_eq!((option.holder, option.writer, option.creator, option.venue), (holder, holder, holder, holder));
+        assert_eq!(option.strike_lamports, 0);
+        assert_eq!(option.refund_decay_start, NOW);
+        assert_eq!(option.schema_version, OPTION_SCHEMA_VERSION);
+        assert_eq!(option.client_nonce, 0);
+        assert!(option.transferable);
+        assert!(option.co_holders.is_empty() && option.nft_mint.is_none() && option.premium_mint.is_none());
+    }
+
+
+    #[test]
+    fn a_reserved_compound_child_is_taken_but_not_a_retry() {
+        let (holder, writer) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut params = test_params();
+        params.writer = Some(writer);
+
+        let mut option = OptionContract::default();
+        reserve_option(&mut option, &params, holder, writer, NOW, 254);
+        assert_eq!(option.status_enum().unwrap(), OptionStatus::Reserved);
+        // create_option sees the address as taken, and the same terms don't
+        // pass for a create that already landed
+        assert_ne!(option.created_at, 0);
+        assert!(!is_create_retry(&option, &params, holder));
+        assert_err(require_writer_payout_allowed(&option), QuorumError::PremiumLocked);
+
+        // Exercising stamps the real terms over the placeholder
+        write_option_terms(&mut option, params.clone(), holder, None, None, NOW, 254);
+        assert_eq!(option.status_enum().unwrap(), OptionStatus::Active);
+        assert!(is_create_retry(&option, &params, holder));
+    }
 }
@@ -23,6 +23,12 @@ const router = Router();
 
 const PROGRAM_ID = process.env.QUORUM_PROGRAM_ID || 'FC1476pqPa9YtMiXVk2QTFMNEjfh8P16HiEM3DihHhqy';
 const RPC_URL = process.env.SOLANA_RPC_URL || 'https://api.devnet.solana.com';
+// Matches OPTION_SEED_VERSION in the program's option PDA seeds
+const OPTION_SEED_VERSION = 1;
+// Face value per ticket the hold is written at — the program rejects a zero strike
+const STRIKE_LAMPORTS = BigInt(process.env.QUORUM_BLINK_STRIKE_LAMPORTS || '100000000');
+// ProgramConfig.treasury follows discriminator(8) + authority(32) + paused + bump + max_per_holder + protocol_fee_bps
+const CONFIG_TREASURY_OFFSET = 46;
 
 // Blink CORS headers — required by Solana Actions spec
 const blinkCors = cors({
@@ -72,6 +78,31 @@ function borshU16(n: number): Buffer {
   return b;
 }
 
+function borshBool(v: boolean): Buffer {
+  return Buffer.from([v ? 1 : 0]);
+}
+
+// Option<T>::None — a single zero tag byte
+function borshNone(): Buffer {
+  return Buffer.from([0]);
+}
+
+// Empty Vec<T> — a zero u32 length
+function borshEmptyVec(): Buffer {
+  return Buffer.alloc(4);
+}
+
+// ─── Event seed (mirrors normalize_event_name + event_seed in the program) ───
+function eventSeed(eventName: string): Buffer {
+  const normalized = eventName.trim().split(/\s+/).join(' ').toLowerCase();
+  return crypto.createHash('sha256').update(normalized, 'utf-8').digest();
+}
+
+async function pda(programAddr: ReturnType<typeof address>, seeds: Buffer[]): Promise<Buffer> {
+  const [addr] = await getProgramDerivedAddress({ programAddress: programAddr, seeds });
+  return base58Decode(String(addr));
+}
+
 // ─── Compact-u16 (Solana wire format) ────────────────────────────────────────
 function compactU16(n: number): Buffer {
   if (n <= 0x7f) return Buffer.from([n]);
@@ -170,22 +201,38 @@ router.post('/:holdId/pay', async (req: Request, res: Response) => {
     const optionId = `${holdId}-${durationStr}-${Date.now()}`;
     const expiry = BigInt(Math.floor(Date.now() / 1000) + config.expirySeconds);
 
-    // Derive PDA: seeds = ["option", optionId]
+    // Derive PDAs. The buyer writes their own hold, so they are also the writer
+    // whose EventRegistry is passed.
     const programAddr = address(PROGRAM_ID);
-    const [pdaAddr] = await getProgramDerivedAddress({
+    const buyerKey = base58Decode(account);
+    const seed = eventSeed(eventName);
+    const pdaKey = await pda(programAddr, [
+      Buffer.from('option'),
+      Buffer.from([OPTION_SEED_VERSION]),
+      Buffer.from(optionId, 'utf-8'),
+      Buffer.alloc(8), // client_nonce 0
+    ]);
+    const [configAddr] = await getProgramDerivedAddress({
       programAddress: programAddr,
-      seeds: [
-        Buffer.from('option'),
-        Buffer.from(optionId, 'utf-8'),
-      ],
+      seeds: [Buffer.from('config')],
     });
-
-    // Fetch recent blockhash
+    const configKey = base58Decode(String(configAddr));
+    const registryKey = await pda(programAddr, [Buffer.from('event'), buyerKey, seed]);
+    const holderStatsKey = await pda(programAddr, [Buffer.from('holder'), buyerKey]);
+    const eventStatsKey = await pda(programAddr, [Buffer.from('stats'), seed]);
+    const eventIndexKey = await pda(programAddr, [Buffer.from('index'), seed]);
+    const globalStatsKey = await pda(programAddr, [Buffer.from('global_stats')]);
+
+    // Fetch recent blockhash and the treasury the config routes fees to
     const rpc = createSolanaRpc(RPC_URL);
     const blockhashResp = await rpc.getLatestBlockhash({ commitment: 'finalized' }).send();
     const blockhash = blockhashResp.value.blockhash as string;
+    const configInfo = await rpc.getAccountInfo(configAddr, { encoding: 'base64' }).send();
+    if (!configInfo.value) throw new Error('Quorum config account not found');
+    const configData = Buffer.from(configInfo.value.data[0], 'base64');
+    const treasuryKey = configData.subarray(CONFIG_TREASURY_OFFSET, CONFIG_TREASURY_OFFSET + 32);
 
-    // Build instruction data
+    // Build instruction data — CreateOptionParams in field order
     const disc = discriminator('create_option');
     const instructionData = Buffer.concat([
       disc,
@@ -194,38 +241,69 @@ router.post('/:holdId/pay', async (req: Request, res: Response) => {
       borshString(eventDate),
       borshString(ticketType),
       borshU8(quantity),
-      borshU64(config.lamports),
+      borshU64(config.lamports), // premium_lamports
+      borshU64(STRIKE_LAMPORTS), // strike_lamports
       borshI64(expiry),
-      borshU16(1000), // venue_royalty_bps = 10%
+      borshU16(1000),            // venue_royalty_bps = 10%
+      borshU16(10_000),          // cancel_refund_bps — "cancel anytime" refunds the full premium
+      borshNone(),               // writer — defaults to the buyer
+      borshNone(),               // venue — defaults to the writer
+      borshI64(BigInt(0)),       // exercise_window_start — American style
+      borshEmptyVec(),           // co_holders — sole holder
+      borshBool(false),          // auto_exercise
+      borshU64(BigInt(0)),       // client_nonce
+      borshNone(),               // transferable — defaults to true
+      borshNone(),               // metadata_uri
+      borshNone(),               // identity_hash
     ]);
 
     // Decode account keys
-    const buyerKey = base58Decode(account);
-    const pdaKey = base58Decode(String(pdaAddr));
     const systemKey = Buffer.from('0000000000000000000000000000000000000000000000000000000000000000', 'hex');
     const programKey = base58Decode(PROGRAM_ID);
     const blockhashBytes = base58Decode(blockhash);
 
-    // Account indices in the message
-    // 0 = buyer (feePayer, writable signer)
-    // 1 = option_contract PDA (writable)
-    // 2 = system_program (readonly)
-    // 3 = program itself (for invocation)
-
-    const accountKeys = [buyerKey, pdaKey, systemKey, programKey];
+    // Message account keys, ordered signer → writable → readonly
+    //  0 = buyer (feePayer, writable signer; holder)
+    //  1 = option_contract PDA      5 = event_stats
+    //  2 = event_registry           6 = event_index
+    //  3 = treasury                 7 = global_stats
+    //  4 = holder_stats             (1-7 writable)
+    //  8 = config, 9 = system_program, 10 = program itself (readonly)
+    const accountKeys = [
+      buyerKey, pdaKey, registryKey, treasuryKey, holderStatsKey, eventStatsKey,
+      eventIndexKey, globalStatsKey, configKey, systemKey, programKey,
+    ];
+
+    // CreateOption accounts in context order. The optional writer_entry, pool
+    // and clock are left out by passing the program ID in their place.
+    const ixAccounts = [
+      1,  // option_contract
+      8,  // config
+      10, // writer_entry (None)
+      2,  // event_registry
+      3,  // treasury
+      10, // pool (None)
+      4,  // holder_stats
+      0,  // holder
+      5,  // event_stats
+      6,  // event_index
+      7,  // global_stats
+      9,  // system_program
+      10, // clock (None)
+    ];
 
     // Build compiled instruction
     const compiledInstruction = Buffer.concat([
-      Buffer.from([3]),               // program_id_index (index 3 = programKey)
-      compactU16(3),                  // account count = 3
-      Buffer.from([0, 1, 2]),         // account indices: buyer, pda, system_program
+      Buffer.from([10]),              // program_id_index (index 10 = programKey)
+      compactU16(ixAccounts.length),
+      Buffer.from(ixAccounts),
       compactU16(instructionData.length),
       instructionData,
     ]);
 
     // Build v0 message
-    // Header: [num_required_signatures=1, num_readonly_signed=0, num_readonly_unsigned=2]
-    const messageHeader = Buffer.from([1, 0, 2]);
+    // Header: [num_required_signatures=1, num_readonly_signed=0, num_readonly_unsigned=3]
+    const messageHeader = Buffer.from([1, 0, 3]);
 
     const accountSection = Buffer.concat([
       compactU16(accountKeys.length),
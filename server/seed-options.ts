@@ -18,6 +18,8 @@ dotenv.config();
 
 const RPC_URL = process.env.SOLANA_RPC_URL || 'https://api.devnet.solana.com';
 const PROGRAM_ID_STR = process.env.QUORUM_PROGRAM_ID || 'FC1476pqPa9YtMiXVk2QTFMNEjfh8P16HiEM3DihHhqy';
+// Matches OPTION_SEED_VERSION in the program's option PDA seeds
+const OPTION_SEED_VERSION = 1;
 
 async function loadWallet(): Promise<Keypair> {
   const keyPath = os.homedir() + '/.config/solana/id.json';
@@ -108,7 +110,7 @@ async function main() {
     try {
       // Derive PDA
       const [pda, bump] = PublicKey.findProgramAddressSync(
-        [Buffer.from('option'), Buffer.from(seed.optionId)],
+        [Buffer.from('option'), Buffer.from([OPTION_SEED_VERSION]), Buffer.from(seed.optionId), Buffer.alloc(8)],
         programId
       );
 